@@ -0,0 +1,43 @@
+use std::fs::File;
+use std::io::BufReader;
+use std::time::Instant;
+
+use rhymalize::ipa_utils::ipa::{english::EnglishSyllableRule, syls_from_word, Word};
+use serde_json::Value;
+
+// parses every transcription in en_US.json, run with cargo bench
+fn main() {
+    let reader = BufReader::new(File::open("./en_US.json").expect("en_US.json is missing"));
+    let lookup: Value = serde_json::from_reader(reader).unwrap();
+    let transcriptions: Vec<&str> = lookup
+        .as_object()
+        .unwrap()
+        .values()
+        .filter_map(|x| x.as_str())
+        .flat_map(|x| x.split(", "))
+        .collect();
+
+    let start = Instant::now();
+    let mut failed = 0;
+    let words: Vec<Word> = transcriptions
+        .iter()
+        .filter_map(|x| {
+            let word = Word::try_from(*x);
+            failed += word.is_err() as usize;
+            word.ok()
+        })
+        .collect();
+    let parsing = start.elapsed();
+
+    let start = Instant::now();
+    let rule = EnglishSyllableRule::default();
+    let syllables: usize = words.iter().map(|x| syls_from_word(x, &rule).len()).sum();
+    let syllabification = start.elapsed();
+
+    println!(
+        "parsed {} transcriptions in {parsing:?} ({:.0}/s), {failed} failed",
+        transcriptions.len(),
+        transcriptions.len() as f64 / parsing.as_secs_f64()
+    );
+    println!("split into {syllables} syllables in {syllabification:?}");
+}
@@ -48,7 +48,7 @@ fn main() {
                 match Word::try_from(j.as_str()) {
                     Ok(t) => {
                         println!("{t}");
-                        let a = syls_from_word(&t, &EnglishSyllableRule {});
+                        let a = syls_from_word(&t, &EnglishSyllableRule::default());
                         for syl in a {
                             println!("{syl}");
                         }
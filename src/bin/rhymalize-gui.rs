@@ -2,31 +2,29 @@ use std::path::Path;
 use std::sync::{Arc, RwLock, Weak};
 use std::{fs, vec};
 
-use anyhow::Context;
-use futures::{SinkExt, Stream, StreamExt};
-use iced::stream::{channel, try_channel};
 use iced::widget::{button, Column, Row};
 use iced::widget::{
     column, container, row, scrollable::Scrollable, text, text_input, Container, MouseArea, Text,
 };
 
-use iced::futures::channel::mpsc;
 use iced::task::Task;
-use iced::{Color, Subscription};
-use rhymalize::ipa_utils::fetching::IpaConverter;
-use rhymalize::ipa_utils::fetching::{json::JsonLookupConverter, wiktionary::WiktionaryConverter};
-use rhymalize::ipa_utils::{self, ipa::*};
+use iced::Color;
+use rhymalize::ipa_utils::fetching::json::JsonLookupConverter;
+use rhymalize::ipa_utils::fetching::letter_to_sound::{FallbackConverter, LetterToSoundConverter};
+use rhymalize::ipa_utils::find_rhyme::{self, PositionedSyllable, SyllablePosition};
+use rhymalize::ipa_utils::{self, ipa::english::EnglishSyllableRule, ipa::*};
 
 #[derive(Debug)]
 struct Rhyme {
     color: Color,
-    members: Vec<Weak<RwLock<DisplaySyllable>>>,
     highlighted: bool,
 }
 #[derive(Debug)]
 struct DisplayWord {
     text: String,
     syllables: Vec<Arc<RwLock<DisplaySyllable>>>,
+    // Some(confident) if the ipa was guessed from the spelling
+    guessed: Option<bool>,
 }
 
 #[derive(Debug, Clone)]
@@ -34,34 +32,16 @@ struct DisplaySyllable {
     syllable: Syllable,
     rhymes: Vec<Arc<RwLock<RhymeSyllable>>>,
 }
-#[allow(dead_code)]
 #[derive(Debug)]
 struct RhymeSyllable {
-    cur: Weak<RwLock<DisplaySyllable>>,
     rhyme: Weak<RwLock<Rhyme>>,
-    prev: Option<Weak<RwLock<DisplaySyllable>>>,
     prev_dist: Option<usize>,
-    next: Option<Weak<RwLock<DisplaySyllable>>>,
     next_dist: Option<usize>,
 }
-#[derive(Debug, Clone)]
-enum GetSylMessage {
-    Started(
-        mpsc::Sender<(
-            Vec<Arc<RwLock<DisplayWord>>>,
-            Arc<RwLock<JsonLookupConverter>>,
-        )>,
-    ),
-    Processed((Arc<RwLock<DisplayWord>>, Vec<DisplaySyllable>)),
-    Finished,
-}
-#[allow(dead_code)]
 struct App {
     raw_text: String,
     text: Vec<Vec<Arc<RwLock<DisplayWord>>>>,
     rhymes: Vec<Arc<RwLock<Rhyme>>>,
-    get_syl: bool,
-    ipa_converter: Arc<RwLock<JsonLookupConverter>>,
     input_field_text: String,
 }
 
@@ -69,15 +49,23 @@ impl App {
     fn calc_rhyme(&mut self) -> Task<Message> {
         self.rhymes = vec![];
 
-        let syls: Vec<Arc<RwLock<DisplaySyllable>>> = self
-            .text
-            .iter()
-            .flat_map(|x| x.iter().map(|y| y.read().unwrap().syllables.clone()))
-            .flatten()
-            .collect();
-
-        for syl in syls.iter() {
-            syl.write().unwrap().rhymes = vec![];
+        let mut syls: Vec<Arc<RwLock<DisplaySyllable>>> = vec![];
+        let mut positioned: Vec<PositionedSyllable> = vec![];
+        for (line_index, line) in self.text.iter().enumerate() {
+            for (word_index, word) in line.iter().enumerate() {
+                for (syl_index, syl) in word.read().unwrap().syllables.iter().enumerate() {
+                    syl.write().unwrap().rhymes = vec![];
+                    positioned.push(PositionedSyllable {
+                        syllable: syl.read().unwrap().syllable.clone(),
+                        position: SyllablePosition {
+                            line: line_index,
+                            word: word_index,
+                            syllable: syl_index,
+                        },
+                    });
+                    syls.push(Arc::clone(syl));
+                }
+            }
         }
 
         let colors = [
@@ -100,166 +88,63 @@ impl App {
             Color::from_rgb8(153, 204, 255),
             Color::from_rgb8(153, 255, 204),
         ];
-        let mut col_index = 0;
 
-        for (i, syl) in syls.iter().enumerate() {
-            {
-                if !syl.read().unwrap().rhymes.is_empty() {
-                    // if syllable has rhyme, continue
-                    continue;
-                }
-            }
+        for (rhyme_index, found) in find_rhyme::find_rhymes(&positioned).iter().enumerate() {
             let new_rhyme = Arc::new(RwLock::new(Rhyme {
-                color: colors[col_index],
-                members: vec![],
+                color: colors[rhyme_index % colors.len()],
                 highlighted: false,
             }));
-            let mut added_root_rhyme = false;
-            let mut last_rhyme_syl = i;
-            for (j, other_syl) in syls.iter().enumerate().skip(i + 1) {
-                //println!("{}, {}", syl.read().unwrap().syllable, other_syl.read().unwrap().syllable);
-                if other_syl.read().unwrap().syllable.nucleus
-                    == syl.read().unwrap().syllable.nucleus
-                {
-                    //
-                    if !added_root_rhyme {
-                        let new_rhyme_syl = Arc::new(RwLock::new(RhymeSyllable {
-                            cur: Arc::downgrade(syl),
-                            rhyme: Arc::downgrade(&new_rhyme),
-                            prev: None,
-                            prev_dist: None,
-                            next: None,
-                            next_dist: None,
-                        }));
-                        new_rhyme.write().unwrap().members.push(Arc::downgrade(syl));
-                        syl.write().unwrap().rhymes.push(new_rhyme_syl);
-                        added_root_rhyme = true;
-                    }
-                    let dist = Some(j - last_rhyme_syl);
-                    if let Some(last_syl) = new_rhyme.read().unwrap().members.last() {
-                        if let Some(rhyme_syl) = last_syl
-                            .upgrade()
-                            .unwrap()
-                            .write()
-                            .unwrap()
-                            .rhymes
-                            .last_mut()
-                        {
-                            rhyme_syl.write().unwrap().next = Some(Arc::downgrade(other_syl));
-                            rhyme_syl.write().unwrap().next_dist = dist;
-                        }
-                    }
-                    let new_rhyme_syl = Arc::new(RwLock::new(RhymeSyllable {
-                        cur: Arc::downgrade(other_syl),
-                        rhyme: Arc::downgrade(&new_rhyme),
-                        prev: new_rhyme.read().unwrap().members.last().map(Weak::clone),
-                        prev_dist: dist,
-                        next: None,
-                        next_dist: None,
-                    }));
-                    new_rhyme
-                        .write()
-                        .unwrap()
-                        .members
-                        .push(Arc::downgrade(other_syl));
-                    other_syl.write().unwrap().rhymes.push(new_rhyme_syl);
-                    last_rhyme_syl = j;
-                }
-            }
-            col_index += 1;
-            if col_index == colors.len() {
-                col_index = 0
-            }
-            if !new_rhyme.read().unwrap().members.is_empty() {
-                self.rhymes.push(Arc::clone(&new_rhyme));
+            for member in found.members.iter() {
+                let new_rhyme_syl = Arc::new(RwLock::new(RhymeSyllable {
+                    rhyme: Arc::downgrade(&new_rhyme),
+                    prev_dist: member.prev_dist,
+                    next_dist: member.next_dist,
+                }));
+                syls[member.index]
+                    .write()
+                    .unwrap()
+                    .rhymes
+                    .push(new_rhyme_syl);
             }
+            self.rhymes.push(new_rhyme);
         }
         Task::none()
     }
 
-    fn get_syl_subscription() -> impl Stream<Item = GetSylMessage> {
-        channel(1, move |mut output| async move {
-            let (sender, mut receiver) = iced::futures::channel::mpsc::channel(100);
-
-            _ = output.send(GetSylMessage::Started(sender)).await;
-
-            let disp_words: Vec<Arc<RwLock<DisplayWord>>>;
-            let ipa_converter_arc: Arc<RwLock<JsonLookupConverter>>;
-            (disp_words, ipa_converter_arc) = receiver.select_next_some().await;
-
-            let ipa_converter = ipa_converter_arc.read().unwrap().clone();
-
-            // disp_words: Vec<Arc<RwLock<DisplayWord>>>,
-            // ipa_converter: Arc<RwLock<JsonLookupConverter>>,
-
-            for arc in disp_words {
-                if let Ok(mut disp_word) = arc.write() {
-                    let word_str = disp_word
-                        .text
-                        .to_ascii_lowercase()
-                        .trim()
-                        .replace([',', '.', '!', '?', ';', '.', ',', '"', '\''], "");
-
-                    let syllables =
-                        App::get_disp_syllables_from_word_str2(&word_str, &ipa_converter)
-                            .unwrap_or_default();
-
-                    disp_word.syllables = syllables;
-                    // _ = output
-                    //     .send(GetSylMessage::Processed((arc.clone(), syllables)))
-                    //     .await;
-                }
-            }
-            _ = output.send(GetSylMessage::Finished).await;
-        })
-    }
-
-    fn get_disp_syllables_from_word_str2(
-        word_str: &str,
-        converter: &impl IpaConverter,
-    ) -> Result<Vec<Arc<RwLock<DisplaySyllable>>>, anyhow::Error> {
-        let ipas = converter.get_ipa_single(word_str)?;
-        let word = ipas.first().with_context(|| "possible ipa vec was empty")?;
-
-        Ok(
-            syls_from_word(&word, &ipa_utils::ipa::english::EnglishSyllableRule)
-                .iter()
-                .map(|z| {
-                    Arc::new(RwLock::new(DisplaySyllable {
+    fn get_disp_syllables_from_word(word: &Word) -> Vec<Arc<RwLock<DisplaySyllable>>> {
+        syls_from_word(word, &Self::syllable_rule())
+            .iter()
+            .map(|z| {
+                Arc::new(RwLock::new(
+                    DisplaySyllable {
                         syllable: z.to_owned(),
                         rhymes: vec![],
-                    })) //Some(Color::from_rgb(1.0, 0.0, 0.0)))
-                })
-                .collect(),
-        )
+                    }, //Some(Color::from_rgb(1.0, 0.0, 0.0)))
+                ))
+            })
+            .collect()
     }
 
-    fn get_disp_syllables_from_word_str(
-        &self,
-        word_str: &str,
-        converter: &impl IpaConverter,
-    ) -> Result<Vec<Arc<RwLock<DisplaySyllable>>>, anyhow::Error> {
-        let ipas = converter.get_ipa_single(word_str)?;
-        let word = ipas.first().with_context(|| "possible ipa vec was empty")?;
+    fn syllable_rule() -> EnglishSyllableRule {
+        EnglishSyllableRule {
+            unmarked_syllabics: true,
+            ..Default::default()
+        }
+    }
 
-        Ok(
-            syls_from_word(&word, &ipa_utils::ipa::english::EnglishSyllableRule)
-                .iter()
-                .map(|z| {
-                    Arc::new(RwLock::new(
-                        DisplaySyllable {
-                            syllable: z.to_owned(),
-                            rhymes: vec![],
-                        }, //Some(Color::from_rgb(1.0, 0.0, 0.0)))
-                    ))
-                })
-                .collect(),
-        )
+    fn english_converter() -> JsonLookupConverter {
+        let mut converter = JsonLookupConverter::new(Path::new("./en_US.json")).unwrap();
+        // en_US.json writes tʃ and dʒ without tie bar
+        converter.parse_options.untied_affricates = true;
+        converter
     }
 
     fn get_syllables(&mut self) -> Task<Message> {
-        let converter = WiktionaryConverter::new();
-        let converter = JsonLookupConverter::new(Path::new("./en_US.json")).unwrap();
+        // words that aren't in the dictionary, like slang and names, are guessed from the spelling
+        let converter = FallbackConverter {
+            primary: App::english_converter(),
+            fallback: LetterToSoundConverter::default(),
+        };
 
         let disp_words = self
             .text
@@ -274,9 +159,15 @@ impl App {
                 .trim()
                 .replace([',', '.', '!', '?', ';', '.', ',', '"', '\''], "");
 
-            word.syllables = self
-                .get_disp_syllables_from_word_str(&word_str, &converter)
-                .unwrap_or_default();
+            (word.syllables, word.guessed) = match converter.get_ipa_with_confidence(&word_str) {
+                Ok((ipas, guessed)) => (
+                    ipas.first()
+                        .map(App::get_disp_syllables_from_word)
+                        .unwrap_or_default(),
+                    guessed,
+                ),
+                Err(_) => (vec![], None),
+            };
         }
 
         Task::none()
@@ -327,6 +218,7 @@ impl App {
                         Arc::new(RwLock::new(DisplayWord {
                             text: word.to_string(),
                             syllables: vec![],
+                            guessed: None,
                         }))
                     })
                     .collect()
@@ -357,7 +249,6 @@ enum Message {
     InputFieldChanged(String),
     SetGeniusLyrics,
     DehighlightRhyme(Weak<RwLock<DisplaySyllable>>),
-    GetSylMessage(GetSylMessage),
 }
 
 impl App {
@@ -372,10 +263,6 @@ impl App {
                 rhymes: vec![],
                 raw_text: text.clone(),
                 text: vec![],
-                get_syl: false,
-                ipa_converter: Arc::new(RwLock::new(
-                    JsonLookupConverter::new(Path::new("./en_US.json")).unwrap(),
-                )),
                 input_field_text: "".into(),
             },
             Task::none(),
@@ -388,30 +275,6 @@ impl App {
 
     fn update(&mut self, message: Message) -> Task<Message> {
         match message {
-            Message::GetSylMessage(m) => match m {
-                GetSylMessage::Started(mut s) => {
-                    let words = self
-                        .text
-                        .iter()
-                        .flat_map(|x| x.iter())
-                        .map(|x| x.clone())
-                        .collect();
-
-                    let converter = self.ipa_converter.clone();
-                    s.send((words, converter));
-                    Task::none()
-                }
-                GetSylMessage::Finished => Task::none(),
-                GetSylMessage::Processed((word_arc, syls)) => {
-                    if let Ok(mut word) = word_arc.write() {
-                        word.syllables = syls
-                            .iter()
-                            .map(|x| Arc::new(RwLock::new(x.clone())))
-                            .collect();
-                    }
-                    Task::none()
-                }
-            },
             Message::LoadText => self.load_text(),
             Message::CalculateRhyme => self.calc_rhyme(),
             Message::GetSyllables => self.get_syllables(),
@@ -492,7 +355,16 @@ impl App {
             row.spacing(5)
         }
         fn make_text_ipa_col<'a>(word: &Arc<RwLock<DisplayWord>>) -> Column<'a, Message> {
-            let text = Text::new(word.read().unwrap().text.clone());
+            // guessed words are grey, and lighter if the guess is unsure
+            let text_color = word.read().unwrap().guessed.map(|confident| {
+                if confident {
+                    Color::from_rgb(0.6, 0.6, 0.6)
+                } else {
+                    Color::from_rgb(0.8, 0.8, 0.8)
+                }
+            });
+            let text = Text::new(word.read().unwrap().text.clone())
+                .style(move |_| iced::widget::text::Style { color: text_color });
             let syl_row = make_syl_row(word.read().unwrap().syllables.clone());
 
             column!(text, syl_row).align_x(iced::Alignment::Center)
@@ -524,15 +396,6 @@ impl App {
         )
         .into()
     }
-
-    fn subscription(&self) -> Subscription<Message> {
-        if self.get_syl {
-            let ipa_converter = self.ipa_converter.clone();
-            Subscription::run(App::get_syl_subscription).map(Message::GetSylMessage)
-        } else {
-            Subscription::none()
-        }
-    }
 }
 
 fn main() -> Result<(), iced::Error> {
@@ -0,0 +1,70 @@
+use rhymalize::ipa_utils::ipa::{evaluation::*, syllable_rule_for_language};
+use std::{env, fs};
+
+// usage: syllable-eval [gold.tsv] [language] [--baseline file] [--save file]
+// the baseline holds the words that were right before, one per line
+fn main() -> anyhow::Result<()> {
+    let mut positional = vec![];
+    let mut baseline = None;
+    let mut save = None;
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--baseline" => baseline = args.next(),
+            "--save" => save = args.next(),
+            _ => positional.push(arg),
+        }
+    }
+    let gold_path = positional.first().map_or("./syllables_en_snapshot.tsv", |x| x);
+    let language = positional.get(1).map_or("en", |x| x);
+
+    let rule = syllable_rule_for_language(language)
+        .ok_or_else(|| anyhow::anyhow!("no syllable rule for {language}"))?;
+    let gold = parse_gold(&fs::read_to_string(gold_path)?)?;
+    let evaluation = Evaluation::new(&gold, rule.as_ref());
+
+    println!(
+        "{}/{} right, accuracy {:.1}%",
+        evaluation.correct.len(),
+        evaluation.total(),
+        evaluation.accuracy() * 100.0
+    );
+    println!("\ntop error patterns:");
+    for (pattern, count) in evaluation.error_patterns().iter().take(10) {
+        let examples: Vec<&str> = evaluation
+            .failures
+            .iter()
+            .filter(|x| x.patterns.iter().any(|x| x == pattern))
+            .take(3)
+            .map(|x| x.label.as_str())
+            .collect();
+        println!("{count:>5}  {pattern}  ({})", examples.join(", "));
+    }
+    println!("\nfailures:");
+    for failure in &evaluation.failures {
+        println!(
+            "{}: expected {}, got {}",
+            failure.label, failure.expected, failure.predicted
+        );
+    }
+
+    if let Some(path) = baseline {
+        let baseline: Vec<String> = fs::read_to_string(path)?
+            .lines()
+            .map(|x| x.trim().to_string())
+            .filter(|x| !x.is_empty())
+            .collect();
+        let regressions = evaluation.regressions(&baseline);
+        println!("\n{} regressions:", regressions.len());
+        for failure in regressions {
+            println!(
+                "{}: expected {}, got {}",
+                failure.label, failure.expected, failure.predicted
+            );
+        }
+    }
+    if let Some(path) = save {
+        fs::write(path, evaluation.correct.join("\n") + "\n")?;
+    }
+    Ok(())
+}
@@ -0,0 +1,259 @@
+use crate::ipa_utils::fetching::*;
+use crate::ipa_utils::ipa::english::EnglishSyllableRule;
+use crate::ipa_utils::ipa::{Letter, ParseOptions, SyllableRule};
+use anyhow::{anyhow, Context, Error};
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader};
+use std::{fs::File, path::Path};
+
+// see http://www.speech.cs.cmu.edu/cgi-bin/cmudict
+
+// vowels carry a stress digit, 0 unstressed, 1 primary and 2 secondary stress
+const ARPABET_VOWEL_LIST: [(&str, &str); 19] = [
+    ("AA", "ɑ"),
+    ("AE", "æ"),
+    ("AH", "ʌ"),
+    ("AO", "ɔ"),
+    ("AW", "aʊ"),
+    ("AX", "ə"),
+    ("AXR", "ɚ"),
+    ("AY", "aɪ"),
+    ("EH", "ɛ"),
+    ("ER", "ɝ"),
+    ("EY", "eɪ"),
+    ("IH", "ɪ"),
+    ("IX", "ɨ"),
+    ("IY", "i"),
+    ("OW", "oʊ"),
+    ("OY", "ɔɪ"),
+    ("UH", "ʊ"),
+    ("UW", "u"),
+    ("UX", "ʉ"),
+];
+
+// unstressed vowels that are reduced in ipa
+const ARPABET_UNSTRESSED_LIST: [(&str, &str); 2] = [("AH", "ə"), ("ER", "ɚ")];
+
+const ARPABET_CONSONANT_LIST: [(&str, &str); 31] = [
+    ("B", "b"),
+    ("CH", "t͡ʃ"),
+    ("D", "d"),
+    ("DH", "ð"),
+    ("DX", "ɾ"),
+    ("EL", "l̩"),
+    ("EM", "m̩"),
+    ("EN", "n̩"),
+    ("F", "f"),
+    ("G", "ɡ"),
+    ("HH", "h"),
+    ("JH", "d͡ʒ"),
+    ("K", "k"),
+    ("L", "l"),
+    ("M", "m"),
+    ("N", "n"),
+    ("NG", "ŋ"),
+    ("NX", "ɾ̃"),
+    ("P", "p"),
+    ("Q", "ʔ"),
+    ("R", "ɹ"),
+    ("S", "s"),
+    ("SH", "ʃ"),
+    ("T", "t"),
+    ("TH", "θ"),
+    ("V", "v"),
+    ("W", "w"),
+    ("WH", "ʍ"),
+    ("Y", "j"),
+    ("Z", "z"),
+    ("ZH", "ʒ"),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Stress {
+    Unstressed,
+    Primary,
+    Secondary,
+}
+
+struct Phone {
+    ipa: &'static str,
+    stress: Option<Stress>,
+}
+
+fn parse_phone(phone: &str) -> Result<Phone, Error> {
+    let (name, stress) = match phone.char_indices().last() {
+        Some((i, '0')) => (&phone[..i], Some(Stress::Unstressed)),
+        Some((i, '1')) => (&phone[..i], Some(Stress::Primary)),
+        Some((i, '2')) => (&phone[..i], Some(Stress::Secondary)),
+        _ => (phone, None),
+    };
+    let list: &[(&str, &'static str)] = match stress {
+        Some(Stress::Unstressed) => &[&ARPABET_UNSTRESSED_LIST[..], &ARPABET_VOWEL_LIST].concat(),
+        Some(_) => &ARPABET_VOWEL_LIST,
+        None => &ARPABET_CONSONANT_LIST,
+    };
+    let ipa = list
+        .iter()
+        .find(|(arpabet, _)| *arpabet == name)
+        .map(|(_, ipa)| *ipa)
+        .with_context(|| format!("unknown arpabet phone {phone}"))?;
+    Ok(Phone { ipa, stress })
+}
+
+// stress marks go in front of the onset, which is as long as the syllable rule allows
+fn arpabet_to_ipa(phones: &[&str], rule: &dyn SyllableRule) -> Result<String, Error> {
+    let phones = phones
+        .iter()
+        .map(|x| parse_phone(x))
+        .collect::<Result<Vec<Phone>, Error>>()?;
+    let letters = phones
+        .iter()
+        .map(|x| Ok(Vec::<Letter>::from(Word::try_from(x.ipa)?)))
+        .collect::<Result<Vec<Vec<Letter>>, Error>>()?;
+
+    let mut marks = vec![None; phones.len()];
+    for (i, phone) in phones.iter().enumerate() {
+        let mark = match phone.stress {
+            Some(Stress::Primary) => "ˈ",
+            Some(Stress::Secondary) => "ˌ",
+            _ => continue,
+        };
+        let mut start = i;
+        let mut onset: Vec<Letter> = vec![];
+        while start > 0 && phones[start - 1].stress.is_none() {
+            let consonant = &letters[start - 1];
+            if !consonant
+                .iter()
+                .rev()
+                .all(|x| rule.makes_valid_onset(x, &onset))
+            {
+                break;
+            }
+            onset.extend(consonant.iter().rev().cloned());
+            start -= 1;
+        }
+        marks[start] = Some(mark);
+    }
+
+    Ok(phones
+        .iter()
+        .zip(marks)
+        .map(|(phone, mark)| mark.unwrap_or_default().to_string() + phone.ipa)
+        .collect())
+}
+
+// old versions comment with ;;;, newer ones with # at the end of the line, words like
+// #HASH-MARK start with a # themselves
+fn strip_comment(line: &str) -> &str {
+    if line.starts_with(";;;") {
+        return "";
+    }
+    let end = line
+        .char_indices()
+        .find(|(i, x)| *x == '#' && line[..*i].ends_with(char::is_whitespace))
+        .map_or(line.len(), |(i, _)| i);
+    &line[..end]
+}
+
+// variants are written as word(2), words like (BEGIN-PARENS start with a bracket themselves
+fn headword(word: &str) -> &str {
+    word.strip_suffix(')')
+        .and_then(|x| x.rsplit_once('('))
+        .filter(|(head, number)| {
+            !head.is_empty() && !number.is_empty() && number.chars().all(|x| x.is_ascii_digit())
+        })
+        .map_or(word, |(head, _)| head)
+}
+
+#[derive(Clone)]
+pub struct CmuDictConverter {
+    pub entries: HashMap<String, Vec<String>>,
+    pub parse_options: ParseOptions,
+}
+
+impl CmuDictConverter {
+    pub fn new(path: &Path) -> Result<Self, Error> {
+        Self::from_reader(BufReader::new(File::open(path)?))
+    }
+
+    pub fn from_reader(reader: impl BufRead) -> Result<Self, Error> {
+        let rule = EnglishSyllableRule::default();
+        let mut entries: HashMap<String, Vec<String>> = HashMap::new();
+        for (number, line) in reader.lines().enumerate() {
+            let line = line?;
+            let line = strip_comment(&line).trim();
+            if line.is_empty() {
+                continue;
+            }
+            let mut parts = line.split_whitespace();
+            let word = parts
+                .next()
+                .with_context(|| format!("line {} has no word", number + 1))?;
+            let word = headword(word).to_lowercase();
+            let phones: Vec<&str> = parts.collect();
+            let ipa =
+                arpabet_to_ipa(&phones, &rule).map_err(|e| anyhow!("line {}: {e}", number + 1))?;
+            entries.entry(word).or_default().push(ipa);
+        }
+        Ok(Self {
+            entries,
+            parse_options: ParseOptions::default(),
+        })
+    }
+}
+
+impl IpaConverter for CmuDictConverter {
+    fn convert_single(&self, input: &str) -> Result<Vec<String>, Error> {
+        self.entries
+            .get(&input.to_lowercase())
+            .cloned()
+            .context(format!("couldn't find \"{}\" in cmudict", input))
+    }
+
+    fn parse_options(&self) -> &ParseOptions {
+        &self.parse_options
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DICT: &str = ";;; some comment
+EXTRA  EH1 K S T R AH0
+ABOUT  AH0 B AW1 T
+hamster HH AE1 M S T ER0
+RECORD  R EH1 K ER0 D
+RECORD(2)  R IH0 K AO1 R D # verb
+CHEESEBURGER  CH IY1 Z B ER2 G ER0
+(BEGIN-PARENS  B IH0 G IH1 N P ER0 EH1 N Z
+#HASH-MARK  HH AE1 SH M AA2 R K
+";
+
+    #[test]
+    fn convert_cmudict() {
+        let converter = CmuDictConverter::from_reader(DICT.as_bytes()).unwrap();
+        assert_eq!(converter.convert_single("extra").unwrap(), ["ˈɛkstɹə"]);
+        assert_eq!(converter.convert_single("About").unwrap(), ["əˈbaʊt"]);
+        assert_eq!(converter.convert_single("hamster").unwrap(), ["ˈhæmstɚ"]);
+        assert_eq!(
+            converter.convert_single("record").unwrap(),
+            ["ˈɹɛkɚd", "ɹɪˈkɔɹd"]
+        );
+        assert_eq!(
+            converter.convert_single("cheeseburger").unwrap(),
+            ["ˈt͡ʃizˌbɝɡɚ"]
+        );
+        // punctuation is spelled out in the dictionary
+        assert_eq!(converter.convert_single("(begin-parens").unwrap().len(), 1);
+        assert_eq!(converter.convert_single("#hash-mark").unwrap().len(), 1);
+        assert!(converter.convert_single("").is_err());
+        assert!(converter.convert_single("missing").is_err());
+        assert_eq!(converter.get_ipa_single("about").unwrap().len(), 1);
+    }
+
+    #[test]
+    fn unknown_phone() {
+        assert!(CmuDictConverter::from_reader("WORD  XX1".as_bytes()).is_err());
+    }
+}
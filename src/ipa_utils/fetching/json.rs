@@ -1,4 +1,5 @@
 use crate::ipa_utils::fetching::*;
+use crate::ipa_utils::ipa::ParseOptions;
 use anyhow::{Context, Error};
 use serde_json::Value;
 use std::io::BufReader;
@@ -7,12 +8,14 @@ use std::{fs::File, path::Path};
 #[derive(Clone)]
 pub struct JsonLookupConverter {
     pub lookup_content: Value,
+    pub parse_options: ParseOptions,
 }
 impl JsonLookupConverter {
     pub fn new(path: &Path) -> Result<Self, Error> {
         let reader = BufReader::new(File::open(path)?);
         Ok(Self {
             lookup_content: serde_json::from_reader(reader)?,
+            parse_options: ParseOptions::default(),
         })
     }
 }
@@ -30,6 +33,10 @@ impl IpaConverter for JsonLookupConverter {
             .collect())
     }
 
+    fn parse_options(&self) -> &ParseOptions {
+        &self.parse_options
+    }
+
     fn convert(&self, inputs: &[&str]) -> Vec<Result<Vec<String>, Error>> {
         inputs
             .iter()
@@ -0,0 +1,378 @@
+use crate::ipa_utils::fetching::*;
+use anyhow::{bail, Error};
+
+// guesses english pronunciations from the spelling, for words that aren't in any dictionary
+
+// graphemes and their most common sound, longer ones first, false where the spelling is often
+// pronounced differently
+const LETTER_TO_SOUND_LIST: [(&str, &str, bool); 67] = [
+    ("ation", "eɪʃən", true),
+    ("tion", "ʃən", true),
+    ("sion", "ʒən", false),
+    ("ough", "ɔ", false),
+    ("augh", "ɔ", true),
+    ("eigh", "eɪ", true),
+    ("igh", "aɪ", true),
+    ("tch", "t͡ʃ", true),
+    ("dge", "d͡ʒ", true),
+    ("sch", "sk", false),
+    ("ar", "ɑɹ", true),
+    ("or", "ɔɹ", true),
+    ("er", "ɝ", true),
+    ("ir", "ɝ", true),
+    ("ur", "ɝ", true),
+    ("ee", "i", true),
+    ("ea", "i", false),
+    ("oo", "u", false),
+    ("ou", "aʊ", false),
+    ("ow", "aʊ", false),
+    ("oi", "ɔɪ", true),
+    ("oy", "ɔɪ", true),
+    ("ai", "eɪ", true),
+    ("ay", "eɪ", true),
+    ("au", "ɔ", true),
+    ("aw", "ɔ", true),
+    ("oa", "oʊ", true),
+    ("ew", "u", true),
+    ("ue", "u", true),
+    ("ie", "i", false),
+    ("ei", "eɪ", false),
+    ("ey", "i", false),
+    ("sh", "ʃ", true),
+    ("ch", "t͡ʃ", false),
+    ("th", "θ", false),
+    ("ph", "f", true),
+    ("wh", "w", true),
+    ("ck", "k", true),
+    ("ng", "ŋ", true),
+    ("qu", "kw", true),
+    ("gh", "", false),
+    ("a", "æ", true),
+    ("e", "ɛ", true),
+    ("i", "ɪ", true),
+    ("o", "ɑ", true),
+    ("u", "ʌ", true),
+    ("y", "ɪ", true),
+    ("b", "b", true),
+    ("c", "k", true),
+    ("d", "d", true),
+    ("f", "f", true),
+    ("g", "ɡ", true),
+    ("h", "h", true),
+    ("j", "d͡ʒ", true),
+    ("k", "k", true),
+    ("l", "l", true),
+    ("m", "m", true),
+    ("n", "n", true),
+    ("p", "p", true),
+    ("q", "k", true),
+    ("r", "ɹ", true),
+    ("s", "s", true),
+    ("t", "t", true),
+    ("v", "v", true),
+    ("w", "w", true),
+    ("x", "ks", true),
+    ("z", "z", true),
+];
+
+// the long vowels of a vowel letter in front of a consonant and a silent e, like in make
+const MAGIC_E_LIST: [(char, &str, bool); 6] = [
+    ('a', "eɪ", true),
+    ('e', "i", true),
+    ('i', "aɪ", true),
+    ('o', "oʊ", false),
+    ('u', "u", true),
+    ('y', "aɪ", true),
+];
+
+// short vowels that become schwa outside of the stressed syllable
+const REDUCED_LIST: [&str; 4] = ["æ", "ɛ", "ɑ", "ʌ"];
+
+const VOWEL_LETTERS: &str = "aeiouy";
+
+// a final s after these is a z, like in dogs
+const VOICED_LETTERS: &str = "bdglmnrvwy";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Guess {
+    pub ipa: String,
+    // false if the spelling is irregular or the word has more than one syllable to stress
+    pub confident: bool,
+}
+
+struct Sound {
+    ipa: &'static str,
+    reliable: bool,
+}
+
+impl Sound {
+    fn is_vowel(&self) -> bool {
+        self.ipa.chars().any(|x| "aæɑɔəɛɝɪiʊuʌoe".contains(x))
+    }
+}
+
+fn is_vowel_letter(letter: Option<&char>) -> bool {
+    letter.is_some_and(|x| VOWEL_LETTERS.contains(*x))
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct LetterToSoundConverter {
+    pub parse_options: ParseOptions,
+}
+
+impl LetterToSoundConverter {
+    // the stress is always put on the first syllable
+    pub fn guess(&self, word: &str) -> Guess {
+        let letters: Vec<char> = word
+            .to_lowercase()
+            .chars()
+            .filter(|x| x.is_ascii_lowercase())
+            .collect();
+        let sounds = sounds(&letters);
+        let vowels = sounds.iter().filter(|x| x.is_vowel()).count();
+
+        let mut ipa = String::from("ˈ");
+        let mut first_vowel = true;
+        for sound in &sounds {
+            if sound.is_vowel() && !first_vowel {
+                ipa += match sound.ipa {
+                    x if REDUCED_LIST.contains(&x) => "ə",
+                    "ɝ" => "ɚ",
+                    x => x,
+                };
+            } else {
+                ipa += sound.ipa;
+            }
+            first_vowel &= !sound.is_vowel();
+        }
+        Guess {
+            ipa,
+            // first syllable stress is wrong for iambic words like admit and defend
+            confident: vowels == 1 && sounds.iter().all(|x| x.reliable),
+        }
+    }
+}
+
+// whether the letter in front of index is voiced, a silent e is skipped
+fn voiced_before(letters: &[char], silent: &[bool], index: usize) -> bool {
+    let before = match index {
+        0 => return false,
+        1 => 0,
+        _ if silent[index - 1] => index - 2,
+        _ => index - 1,
+    };
+    VOICED_LETTERS.contains(letters[before])
+}
+
+fn sounds(letters: &[char]) -> Vec<Sound> {
+    let end = letters.len();
+    let vowel_letters = letters
+        .iter()
+        .filter(|x| VOWEL_LETTERS.contains(**x))
+        .count();
+    let mut silent = vec![false; end];
+    let mut out = vec![];
+    let mut i = 0;
+    while i < end {
+        let letter = letters[i];
+        let next = letters.get(i + 1);
+        let rest: String = letters[i..].iter().collect();
+        if silent[i] {
+            i += 1;
+            continue;
+        }
+        // double consonants are said once
+        if next == Some(&letter) && !VOWEL_LETTERS.contains(letter) {
+            i += 1;
+            continue;
+        }
+        // a vowel, one consonant and a final e or es
+        let magic_e = letters.get(i + 2) == Some(&'e')
+            && (i + 3 == end || (i + 4 == end && letters[i + 3] == 's'))
+            && !is_vowel_letter(next)
+            && (i == 0 || !is_vowel_letter(letters.get(i - 1)))
+            && (i > 0 || letter != 'y');
+        if let Some((_, ipa, reliable)) = MAGIC_E_LIST
+            .iter()
+            .find(|(vowel, _, _)| *vowel == letter && magic_e)
+        {
+            out.push(Sound {
+                ipa,
+                reliable: *reliable,
+            });
+            silent[i + 2] = true;
+            i += 1;
+            continue;
+        }
+        let sound = match letter {
+            // table, little
+            'l' if rest == "le" && i > 0 && !is_vowel_letter(letters.get(i - 1)) => {
+                Some(("əl", true, 2))
+            }
+            'e' if i + 1 == end && vowel_letters > 1 => Some(("", true, 1)),
+            'e' if i + 1 == end => Some(("i", true, 1)),
+            'c' if next.is_some_and(|x| "eiy".contains(*x)) => Some(("s", true, 1)),
+            'g' if next.is_some_and(|x| "eiy".contains(*x)) => Some(("d͡ʒ", false, 1)),
+            'y' if i == 0 && is_vowel_letter(next) => Some(("j", true, 1)),
+            'y' if i + 1 == end && letters[..i].iter().any(|x| "aeiou".contains(*x)) => {
+                Some(("i", true, 1))
+            }
+            'y' if i + 1 == end => Some(("aɪ", true, 1)),
+            's' if i + 1 == end && voiced_before(letters, &silent, i) => Some(("z", true, 1)),
+            // r coloured vowels only in front of consonants, so very keeps its ɛ
+            'a' | 'o' | 'e' | 'i' | 'u'
+                if next == Some(&'r')
+                    && (is_vowel_letter(letters.get(i + 2))
+                        || letters.get(i + 2) == Some(&'r')) =>
+            {
+                None
+            }
+            _ => LETTER_TO_SOUND_LIST
+                .iter()
+                .find(|(grapheme, _, _)| rest.starts_with(grapheme))
+                .map(|(grapheme, ipa, reliable)| (*ipa, *reliable, grapheme.len())),
+        };
+        let (ipa, reliable, len) = sound.unwrap_or_else(|| {
+            let (_, ipa, reliable) = LETTER_TO_SOUND_LIST
+                .iter()
+                .find(|(grapheme, _, _)| grapheme.len() == 1 && grapheme.starts_with(letter))
+                .expect("every letter has a sound");
+            (*ipa, *reliable, 1)
+        });
+        if !ipa.is_empty() {
+            out.push(Sound { ipa, reliable });
+        }
+        i += len;
+    }
+    out
+}
+
+impl LetterToSoundConverter {
+    fn checked_guess(&self, input: &str) -> Result<Guess, Error> {
+        if !input.chars().any(|x| x.is_ascii_alphabetic()) {
+            bail!("\"{input}\" has no letters to guess from");
+        }
+        Ok(self.guess(input))
+    }
+}
+
+impl IpaConverter for LetterToSoundConverter {
+    fn convert_single(&self, input: &str) -> Result<Vec<String>, Error> {
+        Ok(vec![self.checked_guess(input)?.ipa])
+    }
+
+    fn parse_options(&self) -> &ParseOptions {
+        &self.parse_options
+    }
+}
+
+// asks the fallback for words the primary converter doesn't know
+#[derive(Debug, Clone)]
+pub struct FallbackConverter<A: IpaConverter, B: IpaConverter> {
+    pub primary: A,
+    pub fallback: B,
+}
+
+impl<A: IpaConverter, B: IpaConverter> IpaConverter for FallbackConverter<A, B> {
+    fn convert_single(&self, input: &str) -> Result<Vec<String>, Error> {
+        self.primary
+            .convert_single(input)
+            .or_else(|_| self.fallback.convert_single(input))
+    }
+
+    // both are parsed with their own options
+    fn get_ipa_single(&self, input: &str) -> Result<Vec<Word>, Error> {
+        self.primary
+            .get_ipa_single(input)
+            .or_else(|_| self.fallback.get_ipa_single(input))
+    }
+}
+
+impl<A: IpaConverter> FallbackConverter<A, LetterToSoundConverter> {
+    // also returns whether the guess is confident, None if the primary converter knew the word
+    pub fn get_ipa_with_confidence(&self, input: &str) -> Result<(Vec<Word>, Option<bool>), Error> {
+        if let Ok(words) = self.primary.get_ipa_single(input) {
+            return Ok((words, None));
+        }
+        let guess = self.fallback.checked_guess(input)?;
+        let word = Word::parse(&guess.ipa, self.fallback.parse_options())?;
+        Ok((vec![word], Some(guess.confident)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ipa_utils::fetching::json::JsonLookupConverter;
+
+    fn guess(word: &str) -> String {
+        LetterToSoundConverter::default().guess(word).ipa
+    }
+
+    #[test]
+    fn regular_spellings() {
+        assert_eq!(guess("cat"), "ˈkæt");
+        assert_eq!(guess("make"), "ˈmeɪk");
+        assert_eq!(guess("bikes"), "ˈbaɪks");
+        assert_eq!(guess("dogs"), "ˈdɑɡz");
+        assert_eq!(guess("shipping"), "ˈʃɪpɪŋ");
+        assert_eq!(guess("city"), "ˈsɪti");
+        assert_eq!(guess("my"), "ˈmaɪ");
+        assert_eq!(guess("little"), "ˈlɪtəl");
+        assert_eq!(guess("station"), "ˈsteɪʃən");
+        assert_eq!(guess("very"), "ˈvɛɹi");
+        assert_eq!(guess("player"), "ˈpleɪɚ");
+    }
+
+    #[test]
+    fn confidence() {
+        let converter = LetterToSoundConverter::default();
+        assert!(converter.guess("flex").confident);
+        // ough has too many pronunciations
+        assert!(!converter.guess("though").confident);
+        // the stress might not be on the first syllable
+        assert!(!converter.guess("admit").confident);
+        assert!(!converter.guess("defend").confident);
+        assert!(!converter.guess("bananarama").confident);
+        assert!(!converter.guess("brr").confident);
+    }
+
+    #[test]
+    fn guesses_parse() {
+        let converter = LetterToSoundConverter::default();
+        for word in [
+            "skrrt",
+            "finna",
+            "yeet",
+            "drip",
+            "Brooklyn",
+            "whatchamacallit",
+        ] {
+            assert!(converter.get_ipa_single(word).is_ok(), "{word}");
+        }
+        assert!(converter.convert_single("?!").is_err());
+    }
+
+    #[test]
+    fn fallback_after_lookup() {
+        let converter = FallbackConverter {
+            primary: JsonLookupConverter {
+                lookup_content: serde_json::json!({ "hater": "/ˈheɪtɝ/" }),
+                parse_options: ParseOptions::default(),
+            },
+            fallback: LetterToSoundConverter::default(),
+        };
+        assert_eq!(converter.convert_single("hater").unwrap(), ["/ˈheɪtɝ/"]);
+        assert_eq!(converter.convert_single("drip").unwrap(), ["ˈdɹɪp"]);
+        assert_eq!(converter.get_ipa_single("drip").unwrap().len(), 1);
+        assert_eq!(converter.get_ipa_with_confidence("hater").unwrap().1, None);
+        let (words, confident) = converter.get_ipa_with_confidence("though").unwrap();
+        assert_eq!(words.len(), 1);
+        assert_eq!(confident, Some(false));
+        assert_eq!(
+            converter.get_ipa_with_confidence("drip").unwrap().1,
+            Some(true)
+        );
+        assert!(converter.get_ipa_with_confidence("?!").is_err());
+    }
+}
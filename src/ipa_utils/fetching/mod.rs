@@ -1,7 +1,9 @@
-use super::ipa::Word;
+use super::ipa::{ParseOptions, Word, DEFAULT_PARSE_OPTIONS};
 use anyhow::Result;
+pub mod cmudict;
 pub mod genius;
 pub mod json;
+pub mod letter_to_sound;
 pub mod wiktionary;
 
 pub trait IpaConverter {
@@ -9,24 +11,30 @@ pub trait IpaConverter {
         inputs.iter().map(|x| self.convert_single(x)).collect()
     }
     fn convert_single(&self, input: &str) -> Result<Vec<String>, anyhow::Error>;
+    fn parse_options(&self) -> &ParseOptions {
+        &DEFAULT_PARSE_OPTIONS
+    }
     fn get_ipa(&self, inputs: &[&str]) -> Vec<Result<Vec<Word>, anyhow::Error>> {
         //inputs.iter().map(|x| self.get_ipa_single(x)).collect()
+        let options = self.parse_options();
         self.convert(inputs)
             .into_iter()
             .map(|c| match c {
                 Ok(vec) => vec
                     .iter()
-                    .map(|str| Word::try_from(str.as_str()))
-                    .collect::<Result<Vec<Word>, _>>(),
+                    .map(|str| Word::parse(str, options))
+                    .collect::<Result<Vec<Word>, _>>()
+                    .map_err(anyhow::Error::from),
                 Err(e) => Err(e),
             })
             .collect::<Vec<Result<Vec<Word>, anyhow::Error>>>()
     }
     fn get_ipa_single(&self, input: &str) -> Result<Vec<Word>, anyhow::Error> {
-        self.convert_single(input)?
+        Ok(self
+            .convert_single(input)?
             .iter()
-            .map(|x| Word::try_from(x.as_str()))
-            .collect::<Result<Vec<Word>, _>>()
+            .map(|x| Word::parse(x, self.parse_options()))
+            .collect::<Result<Vec<Word>, _>>()?)
     }
 }
 
@@ -1,4 +1,5 @@
 use super::IpaConverter;
+use crate::ipa_utils::ipa::broad::TranscriptionKind;
 use anyhow::bail;
 use anyhow::{anyhow, Context, Error};
 use async_recursion::async_recursion;
@@ -126,19 +127,7 @@ impl WiktionaryConverter {
         Ok(pron_sec.to_string())
     }
     async fn extract_ipa_from_pron(&self, text: &str) -> Result<Vec<String>, anyhow::Error> {
-        let prons: Vec<String> = text
-            .to_string()
-            .split("{{")
-            .filter(|x| x.starts_with("IPA|en|") || x.starts_with("IPA-lite|en|"))
-            .map(|z| z.split_once("}}"))
-            .collect::<Option<Vec<_>>>()
-            .with_context(|| "didn't find ending parenthesis")?
-            .iter()
-            .flat_map(|(a, _)| a.split('|'))
-            .skip(2)
-            .filter(|x| x.starts_with('/') || x.starts_with('['))
-            .map(|x| x.to_string())
-            .collect();
+        let prons = ipa_from_pron(text)?;
         if !prons.is_empty() {
             Ok(prons)
         } else {
@@ -384,6 +373,29 @@ impl WiktionaryConverter {
     //     Ok(res)
     // }
 }
+// the transcriptions of the IPA templates, the broad ones if there are both
+fn ipa_from_pron(text: &str) -> Result<Vec<String>, anyhow::Error> {
+    let prons: Vec<(TranscriptionKind, String)> = text
+        .split("{{")
+        .filter(|x| x.starts_with("IPA|en|") || x.starts_with("IPA-lite|en|"))
+        .map(|z| z.split_once("}}"))
+        .collect::<Option<Vec<_>>>()
+        .with_context(|| "didn't find ending parenthesis")?
+        .iter()
+        .flat_map(|(a, _)| a.split('|'))
+        .skip(2)
+        .filter_map(|x| Some((TranscriptionKind::of(x)?, x.to_string())))
+        .collect();
+    let broad = prons
+        .iter()
+        .any(|(kind, _)| *kind == TranscriptionKind::Phonemic);
+    Ok(prons
+        .into_iter()
+        .filter(|(kind, _)| !broad || *kind == TranscriptionKind::Phonemic)
+        .map(|(_, x)| x)
+        .collect())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -398,4 +410,13 @@ mod tests {
             assert!(ipa.is_ok(), "couldnt convert \"{}\": {:?}", &words[i], ipa)
         }
     }
+
+    #[test]
+    fn prefer_broad_transcriptions() {
+        let text = "===Pronunciation===\n* {{IPA|en|/kæt/|[kʰæt]|a=US}}\n";
+        assert_eq!(ipa_from_pron(text).unwrap(), ["/kæt/"]);
+        let text = "* {{IPA|en|[kʰæt]}}\n* {{IPA-lite|en|[kʰæʔ]}}";
+        assert_eq!(ipa_from_pron(text).unwrap(), ["[kʰæt]", "[kʰæʔ]"]);
+        assert!(ipa_from_pron("{{IPA|en|/kæt/").is_err());
+    }
 }
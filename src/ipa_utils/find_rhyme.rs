@@ -1 +1,234 @@
+use super::ipa::{Letter, Syllable, SyllableWeight};
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SyllablePosition {
+    pub line: usize,
+    pub word: usize,
+    pub syllable: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct PositionedSyllable {
+    pub syllable: Syllable,
+    pub position: SyllablePosition,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RhymeMember {
+    // index into the syllables the rhyme was searched in
+    pub index: usize,
+    pub position: SyllablePosition,
+    pub prev_dist: Option<usize>,
+    pub next_dist: Option<usize>,
+    pub weight: SyllableWeight,
+}
+
+impl RhymeMember {
+    pub fn min_dist(&self) -> Option<usize> {
+        [self.prev_dist, self.next_dist].into_iter().flatten().min()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Rhyme {
+    pub nucleus: Vec<Letter>,
+    pub members: Vec<RhymeMember>,
+}
+
+// flattens lines of words of syllables, keeping track of where each syllable came from
+pub fn positioned_syllables(lines: &[Vec<Vec<Syllable>>]) -> Vec<PositionedSyllable> {
+    lines
+        .iter()
+        .enumerate()
+        .flat_map(|(line, words)| {
+            words.iter().enumerate().flat_map(move |(word, syls)| {
+                syls.iter()
+                    .enumerate()
+                    .map(move |(syllable, syl)| PositionedSyllable {
+                        syllable: syl.clone(),
+                        position: SyllablePosition {
+                            line,
+                            word,
+                            syllable,
+                        },
+                    })
+            })
+        })
+        .collect()
+}
+
+// groups syllables with the same nucleus, distances are counted in syllables
+pub fn find_rhymes(syllables: &[PositionedSyllable]) -> Vec<Rhyme> {
+    find_rhymes_with(syllables, |a, b| a.nucleus == b.nucleus)
+}
+
+// groups syllables that are at least threshold similar to the first one of the group
+pub fn find_near_rhymes(syllables: &[PositionedSyllable], threshold: f32) -> Vec<Rhyme> {
+    find_rhymes_with(syllables, |a, b| a.similarity(b) >= threshold)
+}
+
+// groups syllables with a custom rule, like sharing features in the coda
+pub fn find_rhymes_with(
+    syllables: &[PositionedSyllable],
+    rhymes_with: impl Fn(&Syllable, &Syllable) -> bool,
+) -> Vec<Rhyme> {
+    let mut rhymes = vec![];
+    let mut in_rhyme = vec![false; syllables.len()];
+
+    for (i, syl) in syllables.iter().enumerate() {
+        if in_rhyme[i] {
+            continue;
+        }
+        let mut members: Vec<RhymeMember> = vec![];
+        for (j, other) in syllables.iter().enumerate().skip(i + 1) {
+            if in_rhyme[j] || !rhymes_with(&syl.syllable, &other.syllable) {
+                continue;
+            }
+            if members.is_empty() {
+                members.push(RhymeMember {
+                    index: i,
+                    position: syl.position,
+                    prev_dist: None,
+                    next_dist: None,
+                    weight: syl.syllable.weight(),
+                });
+                in_rhyme[i] = true;
+            }
+            let last = members.last_mut().unwrap();
+            let dist = j - last.index;
+            last.next_dist = Some(dist);
+            members.push(RhymeMember {
+                index: j,
+                position: other.position,
+                prev_dist: Some(dist),
+                next_dist: None,
+                weight: other.syllable.weight(),
+            });
+            in_rhyme[j] = true;
+        }
+        if !members.is_empty() {
+            rhymes.push(Rhyme {
+                nucleus: syl.syllable.nucleus.clone(),
+                members,
+            });
+        }
+    }
+    rhymes
+}
+
+// the weight of every syllable by line, the rhythm of a flow
+pub fn weights_per_line(syllables: &[PositionedSyllable]) -> Vec<Vec<SyllableWeight>> {
+    let mut out: Vec<Vec<SyllableWeight>> = vec![];
+    for syl in syllables {
+        if out.len() <= syl.position.line {
+            out.resize(syl.position.line + 1, vec![]);
+        }
+        out[syl.position.line].push(syl.syllable.weight());
+    }
+    out
+}
+
+// lines with about the same number of morae take about the same time to say
+pub fn morae_per_line(syllables: &[PositionedSyllable]) -> Vec<usize> {
+    let mut out: Vec<usize> = vec![];
+    for syl in syllables {
+        if out.len() <= syl.position.line {
+            out.resize(syl.position.line + 1, 0);
+        }
+        out[syl.position.line] += syl.syllable.morae();
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ipa_utils::ipa::{english::EnglishSyllableRule, syls_from_word, Word};
+
+    fn lines_from(lines: &[&[&str]]) -> Vec<Vec<Vec<Syllable>>> {
+        lines
+            .iter()
+            .map(|words| {
+                words
+                    .iter()
+                    .map(|w| {
+                        syls_from_word(
+                            &Word::try_from(*w).unwrap(),
+                            &EnglishSyllableRule::default(),
+                        )
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn positions() {
+        let syls = positioned_syllables(&lines_from(&[&["ˈhæpi", "kæt"], &["bæt"]]));
+        let positions: Vec<_> = syls
+            .iter()
+            .map(|s| (s.position.line, s.position.word, s.position.syllable))
+            .collect();
+        assert_eq!(positions, [(0, 0, 0), (0, 0, 1), (0, 1, 0), (1, 0, 0)]);
+    }
+
+    #[test]
+    fn groups_by_nucleus() {
+        let syls = positioned_syllables(&lines_from(&[&["kæt", "dɔɡ", "hæt"], &["bɔl", "mæt"]]));
+        let rhymes = find_rhymes(&syls);
+        assert_eq!(rhymes.len(), 2);
+
+        let indices: Vec<_> = rhymes[0].members.iter().map(|m| m.index).collect();
+        assert_eq!(indices, [0, 2, 4]);
+        let dists: Vec<_> = rhymes[0]
+            .members
+            .iter()
+            .map(|m| (m.prev_dist, m.next_dist))
+            .collect();
+        assert_eq!(
+            dists,
+            [(None, Some(2)), (Some(2), Some(2)), (Some(2), None)]
+        );
+        assert_eq!(rhymes[0].members[2].position.line, 1);
+
+        let indices: Vec<_> = rhymes[1].members.iter().map(|m| m.index).collect();
+        assert_eq!(indices, [1, 3]);
+        assert_eq!(rhymes[1].members[0].min_dist(), Some(2));
+    }
+
+    #[test]
+    fn no_rhyme_without_partner() {
+        let syls = positioned_syllables(&lines_from(&[&["kæt", "dɔɡ"]]));
+        assert!(find_rhymes(&syls).is_empty());
+    }
+
+    #[test]
+    fn weights_and_morae() {
+        let syls = positioned_syllables(&lines_from(&[&["ˈsɪti", "kæt"], &["ˈtaɪm"]]));
+        use SyllableWeight::*;
+        assert_eq!(
+            weights_per_line(&syls),
+            [vec![Light, Light, Heavy], vec![Superheavy]]
+        );
+        assert_eq!(morae_per_line(&syls), [4, 3]);
+
+        let syls = positioned_syllables(&lines_from(&[&["kæt", "ˈbiː", "hæt"]]));
+        let weights: Vec<_> = find_rhymes(&syls)[0]
+            .members
+            .iter()
+            .map(|m| m.weight)
+            .collect();
+        assert_eq!(weights, [Heavy, Heavy]);
+    }
+
+    #[test]
+    fn near_rhymes() {
+        let syls = positioned_syllables(&lines_from(&[&["kæt", "bɛt", "dɔɡz"], &["sæt"]]));
+        assert_eq!(find_rhymes(&syls).len(), 1);
+
+        let rhymes = find_near_rhymes(&syls, 0.8);
+        assert_eq!(rhymes.len(), 1);
+        let indices: Vec<_> = rhymes[0].members.iter().map(|m| m.index).collect();
+        assert_eq!(indices, [0, 1, 3]);
+    }
+}
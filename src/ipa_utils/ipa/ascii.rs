@@ -0,0 +1,1490 @@
+use crate::ipa_utils::ipa::*;
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+// see https://en.wikipedia.org/wiki/X-SAMPA and https://en.wikipedia.org/wiki/Kirshenbaum
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AsciiAlphabet {
+    XSampa,
+    Kirshenbaum,
+}
+
+// the letter, x-sampa, kirshenbaum, empty if the alphabet has no symbol for it
+const ASCII_VOWEL_LIST: [(Vowel, &str, &str); 33] = [
+    (
+        Vowel {
+            height: VowelHeight::Close,
+            backness: VowelBackness::Front,
+            roundedness: VowelRoundedness::Unrounded,
+        },
+        "i",
+        "i",
+    ),
+    (
+        Vowel {
+            height: VowelHeight::Close,
+            backness: VowelBackness::Front,
+            roundedness: VowelRoundedness::Rounded,
+        },
+        "y",
+        "y",
+    ),
+    (
+        Vowel {
+            height: VowelHeight::Close,
+            backness: VowelBackness::Central,
+            roundedness: VowelRoundedness::Unrounded,
+        },
+        "1",
+        "i\"",
+    ),
+    (
+        Vowel {
+            height: VowelHeight::Close,
+            backness: VowelBackness::Central,
+            roundedness: VowelRoundedness::Rounded,
+        },
+        "}",
+        "u\"",
+    ),
+    (
+        Vowel {
+            height: VowelHeight::Close,
+            backness: VowelBackness::Back,
+            roundedness: VowelRoundedness::Unrounded,
+        },
+        "M",
+        "u-",
+    ),
+    (
+        Vowel {
+            height: VowelHeight::Close,
+            backness: VowelBackness::Back,
+            roundedness: VowelRoundedness::Rounded,
+        },
+        "u",
+        "u",
+    ),
+    (
+        Vowel {
+            height: VowelHeight::NearClose,
+            backness: VowelBackness::Front,
+            roundedness: VowelRoundedness::Unrounded,
+        },
+        "I",
+        "I",
+    ),
+    (
+        Vowel {
+            height: VowelHeight::NearClose,
+            backness: VowelBackness::Front,
+            roundedness: VowelRoundedness::Rounded,
+        },
+        "Y",
+        "I.",
+    ),
+    (
+        Vowel {
+            height: VowelHeight::NearClose,
+            backness: VowelBackness::Back,
+            roundedness: VowelRoundedness::Rounded,
+        },
+        "U",
+        "U",
+    ),
+    (
+        Vowel {
+            height: VowelHeight::CloseMid,
+            backness: VowelBackness::Front,
+            roundedness: VowelRoundedness::Unrounded,
+        },
+        "e",
+        "e",
+    ),
+    (
+        Vowel {
+            height: VowelHeight::CloseMid,
+            backness: VowelBackness::Front,
+            roundedness: VowelRoundedness::Rounded,
+        },
+        "2",
+        "Y",
+    ),
+    (
+        Vowel {
+            height: VowelHeight::CloseMid,
+            backness: VowelBackness::Central,
+            roundedness: VowelRoundedness::Unrounded,
+        },
+        "@\\",
+        "",
+    ),
+    (
+        Vowel {
+            height: VowelHeight::CloseMid,
+            backness: VowelBackness::Central,
+            roundedness: VowelRoundedness::Rounded,
+        },
+        "8",
+        "",
+    ),
+    (
+        Vowel {
+            height: VowelHeight::CloseMid,
+            backness: VowelBackness::Back,
+            roundedness: VowelRoundedness::Unrounded,
+        },
+        "7",
+        "o-",
+    ),
+    (
+        Vowel {
+            height: VowelHeight::CloseMid,
+            backness: VowelBackness::Back,
+            roundedness: VowelRoundedness::Rounded,
+        },
+        "o",
+        "o",
+    ),
+    (
+        Vowel {
+            height: VowelHeight::Mid,
+            backness: VowelBackness::Central,
+            roundedness: VowelRoundedness::Unrounded,
+        },
+        "@",
+        "@",
+    ),
+    // the mid vowels have no letter of their own and are lowered close mid vowels
+    (
+        Vowel {
+            height: VowelHeight::Mid,
+            backness: VowelBackness::Front,
+            roundedness: VowelRoundedness::Unrounded,
+        },
+        "e_o",
+        "",
+    ),
+    (
+        Vowel {
+            height: VowelHeight::Mid,
+            backness: VowelBackness::Front,
+            roundedness: VowelRoundedness::Rounded,
+        },
+        "2_o",
+        "",
+    ),
+    (
+        Vowel {
+            height: VowelHeight::Mid,
+            backness: VowelBackness::Back,
+            roundedness: VowelRoundedness::Unrounded,
+        },
+        "7_o",
+        "",
+    ),
+    (
+        Vowel {
+            height: VowelHeight::Mid,
+            backness: VowelBackness::Back,
+            roundedness: VowelRoundedness::Rounded,
+        },
+        "o_o",
+        "",
+    ),
+    (
+        Vowel {
+            height: VowelHeight::Open,
+            backness: VowelBackness::Central,
+            roundedness: VowelRoundedness::Unrounded,
+        },
+        "a_\"",
+        "",
+    ),
+    (
+        Vowel {
+            height: VowelHeight::OpenMid,
+            backness: VowelBackness::Front,
+            roundedness: VowelRoundedness::Unrounded,
+        },
+        "E",
+        "E",
+    ),
+    (
+        Vowel {
+            height: VowelHeight::OpenMid,
+            backness: VowelBackness::Front,
+            roundedness: VowelRoundedness::Rounded,
+        },
+        "9",
+        "W",
+    ),
+    (
+        Vowel {
+            height: VowelHeight::OpenMid,
+            backness: VowelBackness::Central,
+            roundedness: VowelRoundedness::Unrounded,
+        },
+        "3",
+        "V\"",
+    ),
+    (
+        Vowel {
+            height: VowelHeight::OpenMid,
+            backness: VowelBackness::Central,
+            roundedness: VowelRoundedness::Rounded,
+        },
+        "3\\",
+        "O\"",
+    ),
+    (
+        Vowel {
+            height: VowelHeight::OpenMid,
+            backness: VowelBackness::Back,
+            roundedness: VowelRoundedness::Unrounded,
+        },
+        "V",
+        "V",
+    ),
+    (
+        Vowel {
+            height: VowelHeight::OpenMid,
+            backness: VowelBackness::Back,
+            roundedness: VowelRoundedness::Rounded,
+        },
+        "O",
+        "O",
+    ),
+    (
+        Vowel {
+            height: VowelHeight::NearOpen,
+            backness: VowelBackness::Front,
+            roundedness: VowelRoundedness::Unrounded,
+        },
+        "{",
+        "&",
+    ),
+    (
+        Vowel {
+            height: VowelHeight::NearOpen,
+            backness: VowelBackness::Central,
+            roundedness: VowelRoundedness::Unrounded,
+        },
+        "6",
+        "",
+    ),
+    (
+        Vowel {
+            height: VowelHeight::Open,
+            backness: VowelBackness::Front,
+            roundedness: VowelRoundedness::Unrounded,
+        },
+        "a",
+        "a",
+    ),
+    (
+        Vowel {
+            height: VowelHeight::Open,
+            backness: VowelBackness::Front,
+            roundedness: VowelRoundedness::Rounded,
+        },
+        "&",
+        "a.",
+    ),
+    (
+        Vowel {
+            height: VowelHeight::Open,
+            backness: VowelBackness::Back,
+            roundedness: VowelRoundedness::Unrounded,
+        },
+        "A",
+        "A",
+    ),
+    (
+        Vowel {
+            height: VowelHeight::Open,
+            backness: VowelBackness::Back,
+            roundedness: VowelRoundedness::Rounded,
+        },
+        "Q",
+        "A.",
+    ),
+];
+
+const ASCII_PULMONIC_CONSONANT_LIST: [(PulmonicConsonant, &str, &str); 78] = [
+    // plosives
+    (
+        PulmonicConsonant {
+            manner: PulmonicConsonantManner::Plosive,
+            place: ConsonantPlace::Bilabial,
+            voicing: ConsonantVoicing::Voiceless,
+        },
+        "p",
+        "p",
+    ),
+    (
+        PulmonicConsonant {
+            manner: PulmonicConsonantManner::Plosive,
+            place: ConsonantPlace::Bilabial,
+            voicing: ConsonantVoicing::Voiced,
+        },
+        "b",
+        "b",
+    ),
+    (
+        PulmonicConsonant {
+            manner: PulmonicConsonantManner::Plosive,
+            place: ConsonantPlace::Aveolar,
+            voicing: ConsonantVoicing::Voiceless,
+        },
+        "t",
+        "t",
+    ),
+    (
+        PulmonicConsonant {
+            manner: PulmonicConsonantManner::Plosive,
+            place: ConsonantPlace::Aveolar,
+            voicing: ConsonantVoicing::Voiced,
+        },
+        "d",
+        "d",
+    ),
+    (
+        PulmonicConsonant {
+            manner: PulmonicConsonantManner::Plosive,
+            place: ConsonantPlace::Retroflex,
+            voicing: ConsonantVoicing::Voiceless,
+        },
+        "t`",
+        "t.",
+    ),
+    (
+        PulmonicConsonant {
+            manner: PulmonicConsonantManner::Plosive,
+            place: ConsonantPlace::Retroflex,
+            voicing: ConsonantVoicing::Voiced,
+        },
+        "d`",
+        "d.",
+    ),
+    (
+        PulmonicConsonant {
+            manner: PulmonicConsonantManner::Plosive,
+            place: ConsonantPlace::Palatal,
+            voicing: ConsonantVoicing::Voiceless,
+        },
+        "c",
+        "c",
+    ),
+    (
+        PulmonicConsonant {
+            manner: PulmonicConsonantManner::Plosive,
+            place: ConsonantPlace::Palatal,
+            voicing: ConsonantVoicing::Voiced,
+        },
+        "J\\",
+        "J",
+    ),
+    (
+        PulmonicConsonant {
+            manner: PulmonicConsonantManner::Plosive,
+            place: ConsonantPlace::Velar,
+            voicing: ConsonantVoicing::Voiceless,
+        },
+        "k",
+        "k",
+    ),
+    (
+        PulmonicConsonant {
+            manner: PulmonicConsonantManner::Plosive,
+            place: ConsonantPlace::Velar,
+            voicing: ConsonantVoicing::Voiced,
+        },
+        "g",
+        "g",
+    ),
+    (
+        PulmonicConsonant {
+            manner: PulmonicConsonantManner::Plosive,
+            place: ConsonantPlace::Uvular,
+            voicing: ConsonantVoicing::Voiceless,
+        },
+        "q",
+        "q",
+    ),
+    (
+        PulmonicConsonant {
+            manner: PulmonicConsonantManner::Plosive,
+            place: ConsonantPlace::Uvular,
+            voicing: ConsonantVoicing::Voiced,
+        },
+        "G\\",
+        "G",
+    ),
+    (
+        PulmonicConsonant {
+            manner: PulmonicConsonantManner::Plosive,
+            place: ConsonantPlace::Glottal,
+            voicing: ConsonantVoicing::Voiceless,
+        },
+        "?",
+        "?",
+    ),
+    // nasals
+    (
+        PulmonicConsonant {
+            manner: PulmonicConsonantManner::Nasal,
+            place: ConsonantPlace::Bilabial,
+            voicing: ConsonantVoicing::Voiced,
+        },
+        "m",
+        "m",
+    ),
+    (
+        PulmonicConsonant {
+            manner: PulmonicConsonantManner::Nasal,
+            place: ConsonantPlace::Labiodental,
+            voicing: ConsonantVoicing::Voiced,
+        },
+        "F",
+        "M",
+    ),
+    (
+        PulmonicConsonant {
+            manner: PulmonicConsonantManner::Nasal,
+            place: ConsonantPlace::Aveolar,
+            voicing: ConsonantVoicing::Voiced,
+        },
+        "n",
+        "n",
+    ),
+    (
+        PulmonicConsonant {
+            manner: PulmonicConsonantManner::Nasal,
+            place: ConsonantPlace::Retroflex,
+            voicing: ConsonantVoicing::Voiced,
+        },
+        "n`",
+        "n.",
+    ),
+    (
+        PulmonicConsonant {
+            manner: PulmonicConsonantManner::Nasal,
+            place: ConsonantPlace::Palatal,
+            voicing: ConsonantVoicing::Voiced,
+        },
+        "J",
+        "n^",
+    ),
+    (
+        PulmonicConsonant {
+            manner: PulmonicConsonantManner::Nasal,
+            place: ConsonantPlace::Velar,
+            voicing: ConsonantVoicing::Voiced,
+        },
+        "N",
+        "N",
+    ),
+    (
+        PulmonicConsonant {
+            manner: PulmonicConsonantManner::Nasal,
+            place: ConsonantPlace::Uvular,
+            voicing: ConsonantVoicing::Voiced,
+        },
+        "N\\",
+        "n\"",
+    ),
+    // trills, taps
+    (
+        PulmonicConsonant {
+            manner: PulmonicConsonantManner::Trill,
+            place: ConsonantPlace::Bilabial,
+            voicing: ConsonantVoicing::Voiced,
+        },
+        "B\\",
+        "b<trl>",
+    ),
+    (
+        PulmonicConsonant {
+            manner: PulmonicConsonantManner::Trill,
+            place: ConsonantPlace::Aveolar,
+            voicing: ConsonantVoicing::Voiced,
+        },
+        "r",
+        "r<trl>",
+    ),
+    (
+        PulmonicConsonant {
+            manner: PulmonicConsonantManner::Trill,
+            place: ConsonantPlace::Uvular,
+            voicing: ConsonantVoicing::Voiced,
+        },
+        "R\\",
+        "r\"",
+    ),
+    (
+        PulmonicConsonant {
+            manner: PulmonicConsonantManner::Tap,
+            place: ConsonantPlace::Aveolar,
+            voicing: ConsonantVoicing::Voiced,
+        },
+        "4",
+        "*",
+    ),
+    (
+        PulmonicConsonant {
+            manner: PulmonicConsonantManner::Tap,
+            place: ConsonantPlace::Retroflex,
+            voicing: ConsonantVoicing::Voiced,
+        },
+        "r`",
+        "*.",
+    ),
+    // fricatives
+    (
+        PulmonicConsonant {
+            manner: PulmonicConsonantManner::NonSibilantFricative,
+            place: ConsonantPlace::Bilabial,
+            voicing: ConsonantVoicing::Voiceless,
+        },
+        "p\\",
+        "P",
+    ),
+    (
+        PulmonicConsonant {
+            manner: PulmonicConsonantManner::NonSibilantFricative,
+            place: ConsonantPlace::Bilabial,
+            voicing: ConsonantVoicing::Voiced,
+        },
+        "B",
+        "B",
+    ),
+    (
+        PulmonicConsonant {
+            manner: PulmonicConsonantManner::NonSibilantFricative,
+            place: ConsonantPlace::Labiodental,
+            voicing: ConsonantVoicing::Voiceless,
+        },
+        "f",
+        "f",
+    ),
+    (
+        PulmonicConsonant {
+            manner: PulmonicConsonantManner::NonSibilantFricative,
+            place: ConsonantPlace::Labiodental,
+            voicing: ConsonantVoicing::Voiced,
+        },
+        "v",
+        "v",
+    ),
+    (
+        PulmonicConsonant {
+            manner: PulmonicConsonantManner::NonSibilantFricative,
+            place: ConsonantPlace::Dental,
+            voicing: ConsonantVoicing::Voiceless,
+        },
+        "T",
+        "T",
+    ),
+    (
+        PulmonicConsonant {
+            manner: PulmonicConsonantManner::NonSibilantFricative,
+            place: ConsonantPlace::Dental,
+            voicing: ConsonantVoicing::Voiced,
+        },
+        "D",
+        "D",
+    ),
+    (
+        PulmonicConsonant {
+            manner: PulmonicConsonantManner::SibilantFricative,
+            place: ConsonantPlace::Aveolar,
+            voicing: ConsonantVoicing::Voiceless,
+        },
+        "s",
+        "s",
+    ),
+    (
+        PulmonicConsonant {
+            manner: PulmonicConsonantManner::SibilantFricative,
+            place: ConsonantPlace::Aveolar,
+            voicing: ConsonantVoicing::Voiced,
+        },
+        "z",
+        "z",
+    ),
+    (
+        PulmonicConsonant {
+            manner: PulmonicConsonantManner::SibilantFricative,
+            place: ConsonantPlace::Postalveolar,
+            voicing: ConsonantVoicing::Voiceless,
+        },
+        "S",
+        "S",
+    ),
+    (
+        PulmonicConsonant {
+            manner: PulmonicConsonantManner::SibilantFricative,
+            place: ConsonantPlace::Postalveolar,
+            voicing: ConsonantVoicing::Voiced,
+        },
+        "Z",
+        "Z",
+    ),
+    (
+        PulmonicConsonant {
+            manner: PulmonicConsonantManner::SibilantFricative,
+            place: ConsonantPlace::Retroflex,
+            voicing: ConsonantVoicing::Voiceless,
+        },
+        "s`",
+        "s.",
+    ),
+    (
+        PulmonicConsonant {
+            manner: PulmonicConsonantManner::SibilantFricative,
+            place: ConsonantPlace::Retroflex,
+            voicing: ConsonantVoicing::Voiced,
+        },
+        "z`",
+        "z.",
+    ),
+    (
+        PulmonicConsonant {
+            manner: PulmonicConsonantManner::SibilantFricative,
+            place: ConsonantPlace::Palatal,
+            voicing: ConsonantVoicing::Voiceless,
+        },
+        "s\\",
+        "S;",
+    ),
+    (
+        PulmonicConsonant {
+            manner: PulmonicConsonantManner::SibilantFricative,
+            place: ConsonantPlace::Palatal,
+            voicing: ConsonantVoicing::Voiced,
+        },
+        "z\\",
+        "Z;",
+    ),
+    (
+        PulmonicConsonant {
+            manner: PulmonicConsonantManner::NonSibilantFricative,
+            place: ConsonantPlace::Palatal,
+            voicing: ConsonantVoicing::Voiceless,
+        },
+        "C",
+        "C",
+    ),
+    (
+        PulmonicConsonant {
+            manner: PulmonicConsonantManner::NonSibilantFricative,
+            place: ConsonantPlace::Palatal,
+            voicing: ConsonantVoicing::Voiced,
+        },
+        "j\\",
+        "C<vcd>",
+    ),
+    (
+        PulmonicConsonant {
+            manner: PulmonicConsonantManner::NonSibilantFricative,
+            place: ConsonantPlace::Velar,
+            voicing: ConsonantVoicing::Voiceless,
+        },
+        "x",
+        "x",
+    ),
+    (
+        PulmonicConsonant {
+            manner: PulmonicConsonantManner::NonSibilantFricative,
+            place: ConsonantPlace::Velar,
+            voicing: ConsonantVoicing::Voiced,
+        },
+        "G",
+        "Q",
+    ),
+    (
+        PulmonicConsonant {
+            manner: PulmonicConsonantManner::NonSibilantFricative,
+            place: ConsonantPlace::Uvular,
+            voicing: ConsonantVoicing::Voiceless,
+        },
+        "X",
+        "X",
+    ),
+    (
+        PulmonicConsonant {
+            manner: PulmonicConsonantManner::NonSibilantFricative,
+            place: ConsonantPlace::Uvular,
+            voicing: ConsonantVoicing::Voiced,
+        },
+        "R",
+        "g\"",
+    ),
+    (
+        PulmonicConsonant {
+            manner: PulmonicConsonantManner::NonSibilantFricative,
+            place: ConsonantPlace::Pharyngeal,
+            voicing: ConsonantVoicing::Voiceless,
+        },
+        "X\\",
+        "H",
+    ),
+    (
+        PulmonicConsonant {
+            manner: PulmonicConsonantManner::NonSibilantFricative,
+            place: ConsonantPlace::Pharyngeal,
+            voicing: ConsonantVoicing::Voiced,
+        },
+        "?\\",
+        "H<vcd>",
+    ),
+    (
+        PulmonicConsonant {
+            manner: PulmonicConsonantManner::NonSibilantFricative,
+            place: ConsonantPlace::Glottal,
+            voicing: ConsonantVoicing::Voiceless,
+        },
+        "h",
+        "h",
+    ),
+    (
+        PulmonicConsonant {
+            manner: PulmonicConsonantManner::NonSibilantFricative,
+            place: ConsonantPlace::Glottal,
+            voicing: ConsonantVoicing::Voiced,
+        },
+        "h\\",
+        "h<?>",
+    ),
+    (
+        PulmonicConsonant {
+            manner: PulmonicConsonantManner::LateralFricative,
+            place: ConsonantPlace::Aveolar,
+            voicing: ConsonantVoicing::Voiceless,
+        },
+        "K",
+        "s<lat>",
+    ),
+    (
+        PulmonicConsonant {
+            manner: PulmonicConsonantManner::LateralFricative,
+            place: ConsonantPlace::Aveolar,
+            voicing: ConsonantVoicing::Voiced,
+        },
+        "K\\",
+        "z<lat>",
+    ),
+    // approximants
+    (
+        PulmonicConsonant {
+            manner: PulmonicConsonantManner::Approximant,
+            place: ConsonantPlace::Labiodental,
+            voicing: ConsonantVoicing::Voiced,
+        },
+        "P",
+        "r<lbd>",
+    ),
+    (
+        PulmonicConsonant {
+            manner: PulmonicConsonantManner::Approximant,
+            place: ConsonantPlace::Aveolar,
+            voicing: ConsonantVoicing::Voiced,
+        },
+        "r\\",
+        "r",
+    ),
+    (
+        PulmonicConsonant {
+            manner: PulmonicConsonantManner::Approximant,
+            place: ConsonantPlace::Retroflex,
+            voicing: ConsonantVoicing::Voiced,
+        },
+        "r\\`",
+        "r.",
+    ),
+    (
+        PulmonicConsonant {
+            manner: PulmonicConsonantManner::Approximant,
+            place: ConsonantPlace::Palatal,
+            voicing: ConsonantVoicing::Voiced,
+        },
+        "j",
+        "j",
+    ),
+    (
+        PulmonicConsonant {
+            manner: PulmonicConsonantManner::Approximant,
+            place: ConsonantPlace::Velar,
+            voicing: ConsonantVoicing::Voiced,
+        },
+        "M\\",
+        "j<vel>",
+    ),
+    (
+        PulmonicConsonant {
+            manner: PulmonicConsonantManner::LateralApproximant,
+            place: ConsonantPlace::Aveolar,
+            voicing: ConsonantVoicing::Voiced,
+        },
+        "l",
+        "l",
+    ),
+    (
+        PulmonicConsonant {
+            manner: PulmonicConsonantManner::LateralApproximant,
+            place: ConsonantPlace::Retroflex,
+            voicing: ConsonantVoicing::Voiced,
+        },
+        "l`",
+        "l.",
+    ),
+    (
+        PulmonicConsonant {
+            manner: PulmonicConsonantManner::LateralApproximant,
+            place: ConsonantPlace::Palatal,
+            voicing: ConsonantVoicing::Voiced,
+        },
+        "L",
+        "l^",
+    ),
+    (
+        PulmonicConsonant {
+            manner: PulmonicConsonantManner::LateralApproximant,
+            place: ConsonantPlace::Velar,
+            voicing: ConsonantVoicing::Voiced,
+        },
+        "L\\",
+        "L",
+    ),
+    (
+        PulmonicConsonant {
+            manner: PulmonicConsonantManner::LateralTap,
+            place: ConsonantPlace::Aveolar,
+            voicing: ConsonantVoicing::Voiced,
+        },
+        "l\\",
+        "*<lat>",
+    ),
+    // epiglottals
+    (
+        PulmonicConsonant {
+            manner: PulmonicConsonantManner::Plosive,
+            place: ConsonantPlace::Pharyngeal,
+            voicing: ConsonantVoicing::Voiceless,
+        },
+        ">\\",
+        "",
+    ),
+    (
+        PulmonicConsonant {
+            manner: PulmonicConsonantManner::Trill,
+            place: ConsonantPlace::Pharyngeal,
+            voicing: ConsonantVoicing::Voiced,
+        },
+        "<\\",
+        "",
+    ),
+    (
+        PulmonicConsonant {
+            manner: PulmonicConsonantManner::Trill,
+            place: ConsonantPlace::Pharyngeal,
+            voicing: ConsonantVoicing::Voiceless,
+        },
+        "H\\",
+        "",
+    ),
+    // letters that are written with a diacritic in ipa too
+    (
+        PulmonicConsonant {
+            manner: PulmonicConsonantManner::Nasal,
+            place: ConsonantPlace::Linguolabial,
+            voicing: ConsonantVoicing::Voiceless,
+        },
+        "n_N",
+        "",
+    ),
+    (
+        PulmonicConsonant {
+            manner: PulmonicConsonantManner::Plosive,
+            place: ConsonantPlace::Labiodental,
+            voicing: ConsonantVoicing::Voiced,
+        },
+        "b_d",
+        "b[",
+    ),
+    (
+        PulmonicConsonant {
+            manner: PulmonicConsonantManner::Plosive,
+            place: ConsonantPlace::Labiodental,
+            voicing: ConsonantVoicing::Voiceless,
+        },
+        "p_d",
+        "p[",
+    ),
+    (
+        PulmonicConsonant {
+            manner: PulmonicConsonantManner::Plosive,
+            place: ConsonantPlace::Linguolabial,
+            voicing: ConsonantVoicing::Voiced,
+        },
+        "d_N",
+        "",
+    ),
+    (
+        PulmonicConsonant {
+            manner: PulmonicConsonantManner::Plosive,
+            place: ConsonantPlace::Linguolabial,
+            voicing: ConsonantVoicing::Voiceless,
+        },
+        "t_N",
+        "",
+    ),
+    (
+        PulmonicConsonant {
+            manner: PulmonicConsonantManner::NonSibilantFricative,
+            place: ConsonantPlace::Linguolabial,
+            voicing: ConsonantVoicing::Voiced,
+        },
+        "D_N",
+        "",
+    ),
+    (
+        PulmonicConsonant {
+            manner: PulmonicConsonantManner::NonSibilantFricative,
+            place: ConsonantPlace::Retroflex,
+            voicing: ConsonantVoicing::Voiced,
+        },
+        "r\\`_r",
+        "",
+    ),
+    (
+        PulmonicConsonant {
+            manner: PulmonicConsonantManner::Approximant,
+            place: ConsonantPlace::Glottal,
+            voicing: ConsonantVoicing::Voiced,
+        },
+        "?_k",
+        "",
+    ),
+    (
+        PulmonicConsonant {
+            manner: PulmonicConsonantManner::Tap,
+            place: ConsonantPlace::Linguolabial,
+            voicing: ConsonantVoicing::Voiced,
+        },
+        "4_N",
+        "",
+    ),
+    (
+        PulmonicConsonant {
+            manner: PulmonicConsonantManner::Tap,
+            place: ConsonantPlace::Uvular,
+            voicing: ConsonantVoicing::Voiced,
+        },
+        "G\\_X",
+        "",
+    ),
+    (
+        PulmonicConsonant {
+            manner: PulmonicConsonantManner::Trill,
+            place: ConsonantPlace::Retroflex,
+            voicing: ConsonantVoicing::Voiced,
+        },
+        "r`r",
+        "*.r<trl>",
+    ),
+    (
+        PulmonicConsonant {
+            manner: PulmonicConsonantManner::LateralApproximant,
+            place: ConsonantPlace::Uvular,
+            voicing: ConsonantVoicing::Voiced,
+        },
+        "L\\_-",
+        "",
+    ),
+    (
+        PulmonicConsonant {
+            manner: PulmonicConsonantManner::LateralTap,
+            place: ConsonantPlace::Palatal,
+            voicing: ConsonantVoicing::Voiced,
+        },
+        "L_X",
+        "",
+    ),
+    (
+        PulmonicConsonant {
+            manner: PulmonicConsonantManner::LateralTap,
+            place: ConsonantPlace::Velar,
+            voicing: ConsonantVoicing::Voiced,
+        },
+        "L\\_X",
+        "",
+    ),
+];
+
+const ASCII_NON_PULMONIC_CONSONANT_LIST: [(NonPulmonicConsonant, &str, &str); 11] = [
+    (
+        NonPulmonicConsonant {
+            kind: NonPulmonicConsonantKind::Click,
+            place: ConsonantPlace::Bilabial,
+        },
+        "O\\",
+        "p!",
+    ),
+    (
+        NonPulmonicConsonant {
+            kind: NonPulmonicConsonantKind::Click,
+            place: ConsonantPlace::Dental,
+        },
+        "|\\",
+        "t!",
+    ),
+    (
+        NonPulmonicConsonant {
+            kind: NonPulmonicConsonantKind::Click,
+            place: ConsonantPlace::Postalveolar,
+        },
+        "!\\",
+        "c!",
+    ),
+    (
+        NonPulmonicConsonant {
+            kind: NonPulmonicConsonantKind::LateralClick,
+            place: ConsonantPlace::Aveolar,
+        },
+        "|\\|\\",
+        "l!",
+    ),
+    (
+        NonPulmonicConsonant {
+            kind: NonPulmonicConsonantKind::Implosive,
+            place: ConsonantPlace::Bilabial,
+        },
+        "b_<",
+        "b`",
+    ),
+    (
+        NonPulmonicConsonant {
+            kind: NonPulmonicConsonantKind::Implosive,
+            place: ConsonantPlace::Aveolar,
+        },
+        "d_<",
+        "d`",
+    ),
+    (
+        NonPulmonicConsonant {
+            kind: NonPulmonicConsonantKind::Click,
+            place: ConsonantPlace::Palatal,
+        },
+        "=\\",
+        "",
+    ),
+    (
+        NonPulmonicConsonant {
+            kind: NonPulmonicConsonantKind::Implosive,
+            place: ConsonantPlace::Retroflex,
+        },
+        "d`_<",
+        "d.`",
+    ),
+    (
+        NonPulmonicConsonant {
+            kind: NonPulmonicConsonantKind::Implosive,
+            place: ConsonantPlace::Palatal,
+        },
+        "J\\_<",
+        "J`",
+    ),
+    (
+        NonPulmonicConsonant {
+            kind: NonPulmonicConsonantKind::Implosive,
+            place: ConsonantPlace::Velar,
+        },
+        "g_<",
+        "g`",
+    ),
+    (
+        NonPulmonicConsonant {
+            kind: NonPulmonicConsonantKind::Implosive,
+            place: ConsonantPlace::Uvular,
+        },
+        "G\\_<",
+        "G`",
+    ),
+];
+
+const ASCII_MISC_LETTER_LIST: [(MiscLetter, &str, &str); 6] = [
+    (MiscLetter::VoicedLabialVelarApproximant, "w", "w"),
+    (MiscLetter::VoicelessLabialVelarApproximant, "W", "w<vls>"),
+    (MiscLetter::VoicedLabialPalatalApproximant, "H", "w<frt>"),
+    (MiscLetter::VoicelessLabialPalatalApproximant, "H_0", ""),
+    (MiscLetter::SjSound, "x\\", ""),
+    (MiscLetter::VelarizedAveolar, "5", ""),
+];
+
+// ejectives are a modifier letter, not a diacritic
+const ASCII_EJECTIVE: (&str, &str, &str) = ("ʼ", "_>", "`");
+
+const ASCII_TIE_BAR: (&str, &str, &str) = ("\u{0361}", "_", "");
+
+const ASCII_DIACRITIC_LIST: [(Diacritic, &str, &str); 36] = [
+    (Diacritic::Syllabic, "=", "-"),
+    (Diacritic::NonSyllabic, "_^", ""),
+    (Diacritic::Aspirated, "_h", "<h>"),
+    (Diacritic::NoAudibleRelease, "_}", ""),
+    (Diacritic::NasalRelease, "_n", ""),
+    (Diacritic::LateralRelease, "_l", ""),
+    (Diacritic::Voiceless, "_0", "<vls>"),
+    (Diacritic::Voiced, "_v", "<vcd>"),
+    (Diacritic::BreathyVoiced, "_t", "<?>"),
+    (Diacritic::CreakyVoiced, "_k", ""),
+    (Diacritic::Dental, "_d", "["),
+    (Diacritic::Linguolabial, "_N", ""),
+    (Diacritic::Apical, "_a", ""),
+    (Diacritic::Laminal, "_m", ""),
+    (Diacritic::Advanced, "_+", ""),
+    (Diacritic::Retracted, "_-", ""),
+    (Diacritic::Centralized, "_\"", ""),
+    (Diacritic::MidCentralized, "_x", ""),
+    (Diacritic::Raised, "_r", ""),
+    (Diacritic::Lowered, "_o", ""),
+    (Diacritic::MoreRounded, "_O", ""),
+    (Diacritic::LessRounded, "_c", ""),
+    (Diacritic::Labialized, "_w", "<w>"),
+    (Diacritic::Palatalized, "'", ";"),
+    (Diacritic::Velarized, "_G", "<vel>"),
+    (Diacritic::Pharyngealized, "_?\\", "<phr>"),
+    (Diacritic::VelarizedOrPharyngealized, "_e", ""),
+    (Diacritic::AdvancedTongueRoot, "_A", ""),
+    (Diacritic::RetractedTongueRoot, "_q", ""),
+    (Diacritic::Nasalized, "~", "~"),
+    (Diacritic::Rhoticity, "`", "<r>"),
+    (Diacritic::Pitch(PitchDiacritic::ExtraHigh), "_T", ""),
+    (Diacritic::Pitch(PitchDiacritic::High), "_H", ""),
+    (Diacritic::Pitch(PitchDiacritic::Mid), "_M", ""),
+    (Diacritic::Pitch(PitchDiacritic::Low), "_L", ""),
+    (Diacritic::Pitch(PitchDiacritic::ExtraLow), "_B", ""),
+];
+
+const ASCII_SUPRASEGMENTAL_LIST: [(Suprasegmental, &str, &str); 12] = [
+    (Suprasegmental::PrimaryStress, "\"", "'"),
+    (Suprasegmental::SecondaryStress, "%", ","),
+    (Suprasegmental::Long, ":", ":"),
+    (Suprasegmental::HalfLong, ":\\", ""),
+    (Suprasegmental::ExtraShort, "_X", ""),
+    (Suprasegmental::SyllableBreak, ".", ""),
+    (Suprasegmental::Linking, "-\\", ""),
+    (Suprasegmental::MinorBreak, "|", ""),
+    (Suprasegmental::MajorBreak, "||", ""),
+    (Suprasegmental::GlobalRise, "<R>", ""),
+    (Suprasegmental::Upstep, "^", ""),
+    (Suprasegmental::Downstep, "!", ""),
+];
+
+impl AsciiAlphabet {
+    fn pick<'a>(&self, (_, x_sampa, kirshenbaum): (&str, &'a str, &'a str)) -> &'a str {
+        match self {
+            AsciiAlphabet::XSampa => x_sampa,
+            AsciiAlphabet::Kirshenbaum => kirshenbaum,
+        }
+    }
+
+    // the symbol of the entry for the key, None if there is none or it is empty
+    fn find<T: PartialEq>(
+        &self,
+        list: &[(T, &'static str, &'static str)],
+        key: &T,
+    ) -> Option<&'static str> {
+        list.iter()
+            .find(|(x, _, _)| x == key)
+            .map(|(_, x, k)| self.pick(("", x, k)))
+            .filter(|x| !x.is_empty())
+    }
+
+    // letters without a symbol of their own are written with the symbol of a related letter,
+    // a voiceless consonant as the voiced one with a ring and an ejective as the voiceless plosive
+    fn letter(&self, ipa_type: &LetterType) -> Option<String> {
+        match ipa_type {
+            LetterType::Vowel(vowel) => self.find(&ASCII_VOWEL_LIST, vowel).map(String::from),
+            LetterType::PulmonicConsonant(consonant) => self
+                .find(&ASCII_PULMONIC_CONSONANT_LIST, consonant)
+                .map(String::from)
+                .or_else(|| {
+                    if consonant.voicing != ConsonantVoicing::Voiceless {
+                        return None;
+                    }
+                    let voiced = PulmonicConsonant {
+                        voicing: ConsonantVoicing::Voiced,
+                        ..consonant.clone()
+                    };
+                    Some(
+                        self.find(&ASCII_PULMONIC_CONSONANT_LIST, &voiced)?
+                            .to_string()
+                            + self.find(&ASCII_DIACRITIC_LIST, &Diacritic::Voiceless)?,
+                    )
+                }),
+            LetterType::NonPulmonicConsonant(consonant) => self
+                .find(&ASCII_NON_PULMONIC_CONSONANT_LIST, consonant)
+                .map(String::from)
+                .or_else(|| {
+                    let NonPulmonicConsonantKind::Ejective(manner) = &consonant.kind else {
+                        return None;
+                    };
+                    let pulmonic = LetterType::PulmonicConsonant(PulmonicConsonant {
+                        manner: manner.clone(),
+                        place: consonant.place.clone(),
+                        voicing: ConsonantVoicing::Voiceless,
+                    });
+                    Some(self.letter(&pulmonic)? + self.pick(ASCII_EJECTIVE))
+                }),
+            LetterType::MiscLetter(misc) => {
+                self.find(&ASCII_MISC_LETTER_LIST, misc).map(String::from)
+            }
+            _ => None,
+        }
+    }
+
+    // every (ipa, ascii) pair of the alphabet
+    fn symbols(&self) -> Vec<(String, String)> {
+        let letters = table_letters().filter_map(|x| Some((x.to_string(), self.letter(&x)?)));
+        let diacritics = ASCII_DIACRITIC_LIST
+            .iter()
+            .map(|(dia, x, k)| (dia.to_string(), *x, *k));
+        let suprasegmentals = ASCII_SUPRASEGMENTAL_LIST
+            .iter()
+            .map(|(sup, x, k)| (sup.to_string(), *x, *k));
+        [&ASCII_EJECTIVE, &ASCII_TIE_BAR]
+            .into_iter()
+            .map(|(ipa, x, k)| (ipa.to_string(), *x, *k))
+            .chain(diacritics)
+            .chain(suprasegmentals)
+            .map(|(ipa, x, k)| (ipa, self.pick(("", x, k)).to_string()))
+            .filter(|(_, ascii)| !ascii.is_empty())
+            .chain(letters)
+            .collect()
+    }
+
+    fn symbol_map(&self) -> &'static HashMap<String, String> {
+        match self {
+            AsciiAlphabet::XSampa => &X_SAMPA_SYMBOLS,
+            AsciiAlphabet::Kirshenbaum => &KIRSHENBAUM_SYMBOLS,
+        }
+    }
+}
+
+// ascii to ipa, built once since from_ascii looks every symbol up
+static X_SAMPA_SYMBOLS: LazyLock<HashMap<String, String>> =
+    LazyLock::new(|| symbol_map(AsciiAlphabet::XSampa));
+static KIRSHENBAUM_SYMBOLS: LazyLock<HashMap<String, String>> =
+    LazyLock::new(|| symbol_map(AsciiAlphabet::Kirshenbaum));
+
+fn symbol_map(alphabet: AsciiAlphabet) -> HashMap<String, String> {
+    alphabet
+        .symbols()
+        .into_iter()
+        .map(|(ipa, ascii)| (ascii, ipa))
+        .collect()
+}
+
+// every letter of the ipa tables
+fn table_letters() -> impl Iterator<Item = LetterType> {
+    let vowels = VOWEL_LIST.iter().map(|(x, _)| LetterType::Vowel(x.clone()));
+    let pulmonic = PULMONIC_CONSONANT_LIST
+        .iter()
+        .map(|(x, _)| LetterType::PulmonicConsonant(x.clone()));
+    let non_pulmonic = NON_PULMONIC_CONSONANT_LIST
+        .iter()
+        .map(|(x, _)| LetterType::NonPulmonicConsonant(x.clone()));
+    let misc = MISC_LETTER_LIST
+        .iter()
+        .map(|(x, _)| LetterType::MiscLetter(*x));
+    vowels.chain(pulmonic).chain(non_pulmonic).chain(misc)
+}
+
+// greedy longest match, so t` is read as ʈ and not as t with rhoticity
+fn longest_match<'a>(
+    value: &str,
+    symbols: &'a HashMap<String, String>,
+) -> Result<Vec<(usize, &'a str)>, IpaParseError> {
+    let longest = symbols.keys().map(String::len).max().unwrap_or(0);
+    let mut out = vec![];
+    let mut rest = value;
+    while !rest.is_empty() {
+        let offset = value.len() - rest.len();
+        let (from, to) = (1..=longest.min(rest.len()))
+            .rev()
+            .filter(|x| rest.is_char_boundary(*x))
+            .find_map(|x| symbols.get_key_value(&rest[..x]))
+            .ok_or_else(|| IpaParseError::UnknownGrapheme {
+                grapheme: rest.chars().take(1).collect(),
+                offset,
+                tried: vec![],
+            })?;
+        out.push((offset, to.as_str()));
+        rest = &rest[from.len()..];
+    }
+    Ok(out)
+}
+
+impl Letter {
+    pub fn to_ascii(&self, alphabet: AsciiAlphabet) -> Result<String, anyhow::Error> {
+        let mut out = match &self.ipa_type {
+            LetterType::Affricate(affricate) => {
+                affricate.first.to_ascii(alphabet)?
+                    + alphabet.pick(ASCII_TIE_BAR)
+                    + &affricate.second.to_ascii(alphabet)?
+            }
+            LetterType::Suprasegmental(sup) => alphabet
+                .find(&ASCII_SUPRASEGMENTAL_LIST, sup)
+                .with_context(|| format!("no {alphabet:?} for {sup}"))?
+                .to_string(),
+            ipa_type => alphabet
+                .letter(ipa_type)
+                .with_context(|| format!("no {alphabet:?} for {ipa_type}"))?,
+        };
+        for diacritic in self.diacritics.iter().flatten() {
+            let ascii = alphabet
+                .find(&ASCII_DIACRITIC_LIST, diacritic)
+                .with_context(|| format!("no {alphabet:?} for {diacritic:?}"))?;
+            out += ascii;
+        }
+        Ok(out)
+    }
+}
+
+impl Word {
+    pub fn to_ascii(&self, alphabet: AsciiAlphabet) -> Result<String, anyhow::Error> {
+        self.0.iter().map(|x| x.to_ascii(alphabet)).collect()
+    }
+
+    pub fn from_ascii(
+        value: &str,
+        alphabet: AsciiAlphabet,
+        options: &ParseOptions,
+    ) -> Result<Self, IpaParseError> {
+        let pieces = longest_match(value, alphabet.symbol_map())?;
+        let ipa: String = pieces.iter().map(|(_, x)| *x).collect();
+        // error offsets point at the ascii symbol the grapheme came from
+        Word::parse(&ipa, options).map_err(|e| {
+            e.map_offset(|offset| {
+                let mut end = 0;
+                pieces
+                    .iter()
+                    .find(|(_, x)| {
+                        end += x.len();
+                        end > offset
+                    })
+                    .map_or(value.len(), |(i, _)| *i)
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_letter_round_trips() {
+        // the linguolabial θ prints its mark first, the retroflex trill as two letters
+        let exceptions = ["̼θ", "ɽr", "ɽ̊r̥"];
+        for alphabet in [AsciiAlphabet::XSampa, AsciiAlphabet::Kirshenbaum] {
+            for ipa_type in table_letters() {
+                let Some(ascii) = alphabet.letter(&ipa_type) else {
+                    continue;
+                };
+                if exceptions.contains(&ipa_type.to_string().as_str()) {
+                    continue;
+                }
+                let word = Word::from_ascii(&ascii, alphabet, &Default::default());
+                assert!(word.is_ok(), "{ascii}: {word:?}");
+                let letters: Vec<_> = word.unwrap().0.into_iter().map(|x| x.ipa_type).collect();
+                assert_eq!(letters, [ipa_type], "{ascii}");
+            }
+        }
+    }
+
+    #[test]
+    fn letters_without_x_sampa() {
+        let missing: Vec<String> = table_letters()
+            .filter(|x| AsciiAlphabet::XSampa.letter(x).is_none())
+            .map(|x| x.to_string())
+            .collect();
+        // extipa letters and the taps and fricatives x-sampa has no symbol for, a new letter has
+        // to get a symbol or be added here
+        assert_eq!(
+            missing,
+            [
+                "\u{30a}ɹ\u{331}˔",
+                "ɹ\u{331}˔",
+                "ð\u{331}",
+                "\u{331}θ",
+                "ⱱ\u{31f}",
+                "ⱱ",
+                "ʡ\u{306}",
+                "𝼅",
+                "ꞎ",
+                "ʎ\u{31d}",
+                "𝼆",
+                "ʟ\u{31d}",
+                "𝼄",
+                "𝼈",
+                "𝼈\u{325}",
+            ]
+        );
+    }
+
+    #[test]
+    fn x_sampa() {
+        let word =
+            Word::from_ascii("\"ekstr@", AsciiAlphabet::XSampa, &Default::default()).unwrap();
+        assert_eq!(word.to_string(), "ˈekstrə");
+        assert_eq!(
+            Word::from_ascii("a#", AsciiAlphabet::XSampa, &Default::default()),
+            Err(IpaParseError::UnknownGrapheme {
+                grapheme: "#".to_string(),
+                offset: 1,
+                tried: vec![],
+            })
+        );
+        // the offset is in the ascii, not in the ipa it was converted to
+        assert_eq!(
+            Word::from_ascii("@tS_", AsciiAlphabet::XSampa, &Default::default()),
+            Err(IpaParseError::DanglingTieBar { offset: 2 })
+        );
+
+        for ipa in ["ˈθɪŋkɪŋ", "t͡ʃɜ˞tʃ", "pʰæ̃n", "ˈʃtuːdi̯ʊm", "ʈɖɳ", "kʼa", "m̥a"]
+        {
+            let word = Word::try_from(ipa).unwrap();
+            let ascii = word.to_ascii(AsciiAlphabet::XSampa).unwrap();
+            let back =
+                Word::from_ascii(&ascii, AsciiAlphabet::XSampa, &Default::default()).unwrap();
+            assert_eq!(back, word, "{ipa} {ascii}");
+        }
+        let word = Word::try_from("ˈθɪŋkɪŋ").unwrap();
+        assert_eq!(word.to_ascii(AsciiAlphabet::XSampa).unwrap(), "\"TINkIN");
+    }
+
+    #[test]
+    fn kirshenbaum() {
+        let word = Word::try_from("ˈθɪŋkɪŋ").unwrap();
+        assert_eq!(
+            word.to_ascii(AsciiAlphabet::Kirshenbaum).unwrap(),
+            "'TINkIN"
+        );
+        let back =
+            Word::from_ascii("'TINkIN", AsciiAlphabet::Kirshenbaum, &Default::default()).unwrap();
+        assert_eq!(back, word);
+        // no tie bar in kirshenbaum
+        let word = Word::try_from("t͡ʃi").unwrap();
+        assert_eq!(word.to_ascii(AsciiAlphabet::Kirshenbaum).unwrap(), "tSi");
+        let options = ParseOptions {
+            untied_affricates: true,
+            ..Default::default()
+        };
+        let back = Word::from_ascii("tSi", AsciiAlphabet::Kirshenbaum, &options).unwrap();
+        assert_eq!(back, word);
+    }
+}
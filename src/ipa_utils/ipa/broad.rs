@@ -0,0 +1,255 @@
+use crate::ipa_utils::ipa::*;
+
+// maps narrow [phonetic] transcriptions to broad /phonemic/ ones, so different transcriptions
+// of the same word compare equal
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TranscriptionKind {
+    Phonemic,
+    Phonetic,
+}
+
+impl TranscriptionKind {
+    // from the delimiters, /kæt/ or [kʰæt]
+    pub fn of(value: &str) -> Option<Self> {
+        let value = value.trim();
+        if value.len() < 2 {
+            None
+        } else if value.starts_with('/') && value.ends_with('/') {
+            Some(Self::Phonemic)
+        } else if value.starts_with('[') && value.ends_with(']') {
+            Some(Self::Phonetic)
+        } else {
+            None
+        }
+    }
+}
+
+// diacritics for details that are predictable in english
+const ENGLISH_PREDICTABLE_LIST: [Diacritic; 26] = [
+    Diacritic::Aspirated,
+    Diacritic::NoAudibleRelease,
+    Diacritic::NasalRelease,
+    Diacritic::LateralRelease,
+    Diacritic::Voiceless,
+    Diacritic::Voiced,
+    Diacritic::BreathyVoiced,
+    Diacritic::CreakyVoiced,
+    Diacritic::Dental,
+    Diacritic::Apical,
+    Diacritic::Laminal,
+    Diacritic::Advanced,
+    Diacritic::Retracted,
+    Diacritic::Centralized,
+    Diacritic::MidCentralized,
+    Diacritic::Raised,
+    Diacritic::Lowered,
+    Diacritic::MoreRounded,
+    Diacritic::LessRounded,
+    Diacritic::Labialized,
+    Diacritic::Palatalized,
+    Diacritic::Velarized,
+    Diacritic::VelarizedOrPharyngealized,
+    Diacritic::Pharyngealized,
+    Diacritic::AdvancedTongueRoot,
+    Diacritic::RetractedTongueRoot,
+];
+
+// allophones and the phoneme they belong to, the more specific ones first
+const ENGLISH_ALLOPHONE_LIST: [(&str, &str); 6] = [
+    // nasal flap in winter
+    ("ɾ\u{0303}", "n"),
+    // the flap can also be a d, but t is more common
+    ("ɾ", "t"),
+    ("ɫ", "l"),
+    ("r", "ɹ"),
+    ("ɻ", "ɹ"),
+    ("ɐ", "ʌ"),
+];
+
+const ENGLISH_DROPPED_LIST: [Suprasegmental; 3] = [
+    Suprasegmental::SyllableBreak,
+    Suprasegmental::Linking,
+    Suprasegmental::HalfLong,
+];
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BroadNormalizer {
+    // diacritics that are removed everywhere
+    pub predictable: Vec<Diacritic>,
+    // a letter with at least the diacritics of the first one becomes the second one
+    pub allophones: Vec<(Letter, Letter)>,
+    pub dropped: Vec<Suprasegmental>,
+    // vowels are nasalized in front of nasal consonants anyway
+    pub denasalize_before_nasals: bool,
+}
+
+impl BroadNormalizer {
+    pub fn english() -> Self {
+        let letter = |x: &str| Letter::try_from(x).expect("allophone table is valid ipa");
+        Self {
+            predictable: ENGLISH_PREDICTABLE_LIST.to_vec(),
+            allophones: ENGLISH_ALLOPHONE_LIST
+                .iter()
+                .map(|(from, to)| (letter(from), letter(to)))
+                .collect(),
+            dropped: ENGLISH_DROPPED_LIST.to_vec(),
+            denasalize_before_nasals: true,
+        }
+    }
+
+    pub fn for_language(code: &str) -> Option<Self> {
+        match code {
+            "en" => Some(Self::english()),
+            _ => None,
+        }
+    }
+
+    pub fn broaden(&self, word: &Word) -> Word {
+        let letters = &word.0;
+        let mut out = vec![];
+        for (i, letter) in letters.iter().enumerate() {
+            if let LetterType::Suprasegmental(sup) = &letter.ipa_type {
+                if self.dropped.contains(sup) {
+                    continue;
+                }
+            }
+            let before_nasal = letters[i + 1..]
+                .iter()
+                .find(|x| !matches!(x.ipa_type, LetterType::Suprasegmental(_)))
+                .is_some_and(is_nasal);
+            out.push(self.broaden_letter(letter, before_nasal));
+        }
+        Word(out)
+    }
+
+    // keeps the first transcription of every broad form
+    pub fn distinct(&self, words: &[Word]) -> Vec<Word> {
+        let mut out: Vec<Word> = vec![];
+        for word in words.iter().map(|x| self.broaden(x)) {
+            if !out.contains(&word) {
+                out.push(word);
+            }
+        }
+        out
+    }
+
+    fn broaden_letter(&self, letter: &Letter, before_nasal: bool) -> Letter {
+        if let LetterType::Affricate(affricate) = &letter.ipa_type {
+            return Letter {
+                ipa_type: LetterType::Affricate(Affricate {
+                    first: Box::new(self.broaden_letter(&affricate.first, false)),
+                    second: Box::new(self.broaden_letter(&affricate.second, before_nasal)),
+                }),
+                diacritics: letter.diacritics.clone(),
+            };
+        }
+        let letter = self.allophone(letter);
+        let diacritics: Vec<Diacritic> = letter
+            .diacritics
+            .iter()
+            .flatten()
+            .filter(|x| !self.predictable.contains(x))
+            .filter(|x| {
+                !(self.denasalize_before_nasals && before_nasal && **x == Diacritic::Nasalized)
+            })
+            .copied()
+            .collect();
+        Letter {
+            ipa_type: letter.ipa_type,
+            diacritics: (!diacritics.is_empty()).then_some(diacritics),
+        }
+    }
+
+    // the diacritics of the allophone are replaced by the ones of the phoneme, the rest stay
+    fn allophone(&self, letter: &Letter) -> Letter {
+        let found = self.allophones.iter().find(|(from, _)| {
+            from.ipa_type == letter.ipa_type
+                && from
+                    .diacritics
+                    .iter()
+                    .flatten()
+                    .all(|x| letter.has_diacritic(*x))
+        });
+        let Some((from, to)) = found else {
+            return letter.clone();
+        };
+        let diacritics: Vec<Diacritic> = letter
+            .diacritics
+            .iter()
+            .flatten()
+            .filter(|x| !from.has_diacritic(**x))
+            .chain(to.diacritics.iter().flatten())
+            .copied()
+            .collect();
+        Letter {
+            ipa_type: to.ipa_type.clone(),
+            diacritics: (!diacritics.is_empty()).then_some(diacritics),
+        }
+    }
+}
+
+fn is_nasal(letter: &Letter) -> bool {
+    matches!(
+        &letter.ipa_type,
+        LetterType::PulmonicConsonant(PulmonicConsonant {
+            manner: PulmonicConsonantManner::Nasal,
+            ..
+        })
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn broad(input: &str) -> Word {
+        BroadNormalizer::english().broaden(&Word::try_from(input).unwrap())
+    }
+
+    #[test]
+    fn transcription_kind() {
+        assert_eq!(
+            TranscriptionKind::of("/kæt/"),
+            Some(TranscriptionKind::Phonemic)
+        );
+        assert_eq!(
+            TranscriptionKind::of(" [kʰæt] "),
+            Some(TranscriptionKind::Phonetic)
+        );
+        assert_eq!(TranscriptionKind::of("kæt"), None);
+        assert_eq!(TranscriptionKind::of("/"), None);
+    }
+
+    #[test]
+    fn narrow_equals_broad() {
+        assert_eq!(broad("[ˈkʰæt]"), broad("/ˈkæt/"));
+        assert_eq!(broad("[ˈbʌɾɚ]"), broad("/ˈbʌtɚ/"));
+        assert_eq!(broad("[ˈfiɫ]"), broad("/ˈfil/"));
+        assert_eq!(broad("[ˈwɪ.ɾ̃ɚ]"), broad("/ˈwɪnɚ/"));
+        assert_eq!(broad("[ˈt͡ʃʰɝt͡ʃ]"), broad("/ˈt͡ʃɝt͡ʃ/"));
+        assert_eq!(broad("[ˈpʰl̥eɪ]"), broad("/ˈpleɪ/"));
+    }
+
+    #[test]
+    fn nasalization() {
+        assert_eq!(broad("[kʰæ̃n]"), broad("/kæn/"));
+        // only predictable in front of a nasal
+        assert_ne!(broad("[bɔ̃]"), broad("/bɔ/"));
+        // the syllabic diacritic is kept
+        assert_eq!(broad("[ˈbʌʔn̩]").to_string(), "ˈbʌʔn\u{030D}");
+    }
+
+    #[test]
+    fn distinct_transcriptions() {
+        let words: Vec<Word> = ["/ˈkæt/", "[ˈkʰæt]", "[ˈkʰæʔ]"]
+            .iter()
+            .map(|x| Word::try_from(*x).unwrap())
+            .collect();
+        let distinct = BroadNormalizer::english().distinct(&words);
+        assert_eq!(distinct.len(), 2);
+        assert_eq!(distinct[0].to_string(), "ˈkæt");
+    }
+}
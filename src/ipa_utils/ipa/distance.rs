@@ -0,0 +1,202 @@
+use crate::ipa_utils::ipa::*;
+
+// all distances are between 0.0 (same) and 1.0 (nothing in common)
+
+const VOWEL_HEIGHT_WEIGHT: f32 = 0.5;
+const VOWEL_BACKNESS_WEIGHT: f32 = 0.3;
+const VOWEL_ROUNDEDNESS_WEIGHT: f32 = 0.2;
+
+const CONSONANT_PLACE_WEIGHT: f32 = 0.4;
+const CONSONANT_MANNER_WEIGHT: f32 = 0.4;
+const CONSONANT_VOICING_WEIGHT: f32 = 0.2;
+
+// every diacritic only one of the letters has
+const DIACRITIC_WEIGHT: f32 = 0.1;
+
+const NUCLEUS_WEIGHT: f32 = 0.7;
+const CODA_WEIGHT: f32 = 0.3;
+
+fn scaled(a: u8, b: u8, max: u8) -> f32 {
+    a.abs_diff(b) as f32 / max as f32
+}
+
+impl Vowel {
+    pub fn distance(&self, other: &Vowel) -> f32 {
+        let height = scaled(
+            self.height.clone() as u8,
+            other.height.clone() as u8,
+            VowelHeight::Open as u8,
+        );
+        let backness = scaled(
+            self.backness.clone() as u8,
+            other.backness.clone() as u8,
+            VowelBackness::Back as u8,
+        );
+        let roundedness = if self.roundedness == other.roundedness {
+            0.0
+        } else {
+            1.0
+        };
+        height * VOWEL_HEIGHT_WEIGHT
+            + backness * VOWEL_BACKNESS_WEIGHT
+            + roundedness * VOWEL_ROUNDEDNESS_WEIGHT
+    }
+}
+
+impl PulmonicConsonantManner {
+    // manners that sound alike are closer together
+    fn group(&self) -> u8 {
+        match self {
+            PulmonicConsonantManner::Plosive => 0,
+            PulmonicConsonantManner::SibilantFricative
+            | PulmonicConsonantManner::NonSibilantFricative
+            | PulmonicConsonantManner::LateralFricative => 1,
+            PulmonicConsonantManner::Nasal => 2,
+            PulmonicConsonantManner::Tap
+            | PulmonicConsonantManner::Trill
+            | PulmonicConsonantManner::LateralTap => 3,
+            PulmonicConsonantManner::Approximant | PulmonicConsonantManner::LateralApproximant => 4,
+        }
+    }
+
+    pub fn distance(&self, other: &PulmonicConsonantManner) -> f32 {
+        if self == other {
+            0.0
+        } else if self.group() == other.group() {
+            0.25
+        } else {
+            0.5 + 0.5 * scaled(self.group(), other.group(), 4)
+        }
+    }
+}
+
+impl ConsonantPlace {
+    pub fn distance(&self, other: &ConsonantPlace) -> f32 {
+        scaled(
+            self.clone() as u8,
+            other.clone() as u8,
+            ConsonantPlace::Glottal as u8,
+        )
+    }
+}
+
+impl PulmonicConsonant {
+    pub fn distance(&self, other: &PulmonicConsonant) -> f32 {
+        let voicing = if self.voicing == other.voicing {
+            0.0
+        } else {
+            1.0
+        };
+        self.place.distance(&other.place) * CONSONANT_PLACE_WEIGHT
+            + self.manner.distance(&other.manner) * CONSONANT_MANNER_WEIGHT
+            + voicing * CONSONANT_VOICING_WEIGHT
+    }
+}
+
+fn diacritic_distance(a: &Option<Vec<Diacritic>>, b: &Option<Vec<Diacritic>>) -> f32 {
+    let a = a.as_deref().unwrap_or_default();
+    let b = b.as_deref().unwrap_or_default();
+    let differing =
+        a.iter().filter(|x| !b.contains(x)).count() + b.iter().filter(|x| !a.contains(x)).count();
+    differing as f32 * DIACRITIC_WEIGHT
+}
+
+impl LetterType {
+    pub fn distance(&self, other: &LetterType) -> f32 {
+        match (self, other) {
+            (LetterType::Vowel(a), LetterType::Vowel(b)) => a.distance(b),
+            (LetterType::PulmonicConsonant(a), LetterType::PulmonicConsonant(b)) => a.distance(b),
+            (LetterType::Affricate(a), LetterType::Affricate(b)) => {
+                (a.first.distance(&b.first) + a.second.distance(&b.second)) / 2.0
+            }
+            // an affricate is still close to its parts, like t͡ʃ and ʃ
+            (LetterType::Affricate(a), b) | (b, LetterType::Affricate(a)) => {
+                let first = a.first.ipa_type.distance(b);
+                let second = a.second.ipa_type.distance(b);
+                (first.min(second) + 0.25).min(1.0)
+            }
+            (a, b) if a == b => 0.0,
+            // otherwise only the sonority tells us something
+            (a, b) => match (a.sonority(), b.sonority()) {
+                (Some(a), Some(b)) => 0.5 + 0.5 * scaled(a, b, 6),
+                _ => 1.0,
+            },
+        }
+    }
+}
+
+impl Letter {
+    pub fn distance(&self, other: &Letter) -> f32 {
+        let distance = self.ipa_type.distance(&other.ipa_type)
+            + diacritic_distance(&self.diacritics, &other.diacritics);
+        distance.min(1.0)
+    }
+}
+
+// edit distance using the letter distance as substitution cost, scaled by the longer sequence
+pub fn letters_distance(a: &[Letter], b: &[Letter]) -> f32 {
+    let longest = a.len().max(b.len());
+    if longest == 0 {
+        return 0.0;
+    }
+    let mut row: Vec<f32> = (0..=b.len()).map(|x| x as f32).collect();
+    for (i, x) in a.iter().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = (i + 1) as f32;
+        for (j, y) in b.iter().enumerate() {
+            let value = (diagonal + x.distance(y))
+                .min(row[j] + 1.0)
+                .min(row[j + 1] + 1.0);
+            diagonal = row[j + 1];
+            row[j + 1] = value;
+        }
+    }
+    row[b.len()] / longest as f32
+}
+
+impl Syllable {
+    // 1.0 for a perfect rhyme, onsets don't matter
+    pub fn similarity(&self, other: &Syllable) -> f32 {
+        let nucleus = letters_distance(&self.nucleus, &other.nucleus);
+        let coda = letters_distance(&self.coda, &other.coda);
+        1.0 - (nucleus * NUCLEUS_WEIGHT + coda * CODA_WEIGHT)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn letter(input: &str) -> Letter {
+        Letter::try_from(input).unwrap()
+    }
+
+    fn syllable(input: &str) -> Syllable {
+        syls_from_word(
+            &Word::try_from(input).unwrap(),
+            &english::EnglishSyllableRule::default(),
+        )
+        .remove(0)
+    }
+
+    #[test]
+    fn letter_distance() {
+        assert_eq!(letter("i").distance(&letter("i")), 0.0);
+        assert!(letter("ɪ").distance(&letter("i")) < letter("ɑ").distance(&letter("i")));
+        assert!(letter("ɛ").distance(&letter("æ")) < letter("ɛ").distance(&letter("u")));
+        assert!(letter("p").distance(&letter("b")) < letter("p").distance(&letter("s")));
+        assert!(letter("i").distance(&letter("i\u{0303}")) > 0.0);
+        assert_eq!(letter("a").distance(&letter("p")), 1.0);
+    }
+
+    #[test]
+    fn syllable_similarity() {
+        let cat = syllable("kæt");
+        assert_eq!(cat.similarity(&syllable("hæt")), 1.0);
+        let bet = cat.similarity(&syllable("bɛt"));
+        let bit = cat.similarity(&syllable("bit"));
+        assert!(bet > bit);
+        assert!(cat.similarity(&syllable("kæp")) > bit);
+        assert!(cat.similarity(&syllable("dɔɡz")) < 0.5);
+    }
+}
@@ -1,98 +1,371 @@
 use crate::ipa_utils::ipa::*;
+use std::sync::LazyLock;
 
-pub struct EnglishSyllableRule;
+#[derive(Debug, Clone, Default)]
+pub struct EnglishSyllableRule {
+    // treat word final l, m, n after an obstruent as syllabic even without the diacritic
+    pub unmarked_syllabics: bool,
+    pub dialect: EnglishDialect,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EnglishDialect {
+    #[default]
+    GeneralAmerican,
+    ReceivedPronunciation,
+    AfricanAmericanVernacular,
+}
 
 // see https://en.wikipedia.org/wiki/English_phonology
 
-impl SyllableRule for EnglishSyllableRule {
-    fn makes_valid_onset(&self, new: &Letter, rest: &Vec<Letter>) -> bool {
-        true
-        // !matches!((rest.as_slice(), new), ())
+// rhotic dialects have diphthongs with ɹ or ɚ where others have centering diphthongs
+const GENERAL_AMERICAN_DIPHTHONG_LIST: [&str; 15] = [
+    "eɪ", "aɪ", "ɔɪ", "aʊ", "oʊ", "ɪɹ", "ɛɹ", "ʊɹ", "ɔɹ", "ɑɹ", "ɪɚ", "ɛɚ", "ʊɚ", "ɔɚ", "ɑɚ",
+];
+
+// triphthongs like in fire and power
+const RECEIVED_PRONUNCIATION_DIPHTHONG_LIST: [&str; 11] = [
+    "eɪ", "aɪ", "ɔɪ", "aʊ", "əʊ", "ɪə", "eə", "ɛə", "ʊə", "aɪə", "aʊə",
+];
+
+// mostly non-rhotic
+const AFRICAN_AMERICAN_VERNACULAR_DIPHTHONG_LIST: [&str; 9] =
+    ["eɪ", "aɪ", "ɔɪ", "aʊ", "oʊ", "ɪə", "ɛə", "ʊə", "ɔə"];
+
+static GENERAL_AMERICAN_INVENTORY: LazyLock<PhonemeInventory> =
+    LazyLock::new(|| PhonemeInventory::english(EnglishDialect::GeneralAmerican));
+static RECEIVED_PRONUNCIATION_INVENTORY: LazyLock<PhonemeInventory> =
+    LazyLock::new(|| PhonemeInventory::english(EnglishDialect::ReceivedPronunciation));
+static AFRICAN_AMERICAN_VERNACULAR_INVENTORY: LazyLock<PhonemeInventory> =
+    LazyLock::new(|| PhonemeInventory::english(EnglishDialect::AfricanAmericanVernacular));
+
+impl EnglishDialect {
+    // diphthongs and triphthongs
+    pub fn diphthongs(&self) -> &'static [&'static str] {
+        match self {
+            EnglishDialect::GeneralAmerican => &GENERAL_AMERICAN_DIPHTHONG_LIST,
+            EnglishDialect::ReceivedPronunciation => &RECEIVED_PRONUNCIATION_DIPHTHONG_LIST,
+            EnglishDialect::AfricanAmericanVernacular => {
+                &AFRICAN_AMERICAN_VERNACULAR_DIPHTHONG_LIST
+            }
+        }
     }
-    fn is_diphthong(&self, first: &Letter, second: &Letter) -> bool {
+
+    pub fn inventory(&self) -> &'static PhonemeInventory {
+        match self {
+            EnglishDialect::GeneralAmerican => &GENERAL_AMERICAN_INVENTORY,
+            EnglishDialect::ReceivedPronunciation => &RECEIVED_PRONUNCIATION_INVENTORY,
+            EnglishDialect::AfricanAmericanVernacular => &AFRICAN_AMERICAN_VERNACULAR_INVENTORY,
+        }
+    }
+}
+
+fn pulmonic(letter: &Letter) -> Option<&PulmonicConsonant> {
+    match &letter.ipa_type {
+        LetterType::PulmonicConsonant(consonant) => Some(consonant),
+        _ => None,
+    }
+}
+
+fn is_s(letter: &Letter) -> bool {
+    pulmonic(letter).is_some_and(|c| {
+        c.manner == PulmonicConsonantManner::SibilantFricative
+            && c.place == ConsonantPlace::Aveolar
+            && c.voicing == ConsonantVoicing::Voiceless
+    })
+}
+
+fn is_sh(letter: &Letter) -> bool {
+    pulmonic(letter).is_some_and(|c| {
+        c.manner == PulmonicConsonantManner::SibilantFricative
+            && c.place == ConsonantPlace::Postalveolar
+            && c.voicing == ConsonantVoicing::Voiceless
+    })
+}
+
+fn is_h(letter: &Letter) -> bool {
+    pulmonic(letter).is_some_and(|c| c.place == ConsonantPlace::Glottal)
+}
+
+fn is_voiceless_plosive(letter: &Letter) -> bool {
+    pulmonic(letter).is_some_and(|c| {
+        c.manner == PulmonicConsonantManner::Plosive && c.voicing == ConsonantVoicing::Voiceless
+    })
+}
+
+fn is_nasal(letter: &Letter) -> bool {
+    pulmonic(letter).is_some_and(|c| c.manner == PulmonicConsonantManner::Nasal)
+}
+
+fn is_ng(letter: &Letter) -> bool {
+    pulmonic(letter).is_some_and(|c| {
+        c.manner == PulmonicConsonantManner::Nasal && c.place == ConsonantPlace::Velar
+    })
+}
+
+fn is_fricative(letter: &Letter) -> bool {
+    pulmonic(letter).is_some_and(|c| {
         matches!(
-            (first, second),
-            (
-                Letter {
-                    ipa_type: LetterType::Vowel(Vowel {
-                        height: VowelHeight::CloseMid,
-                        backness: VowelBackness::Front,
-                        roundedness: VowelRoundedness::Unrounded,
-                    }),
-                    diacritics: None,
-                } | Letter {
-                    ipa_type: LetterType::Vowel(Vowel {
-                        height: VowelHeight::Open,
-                        backness: VowelBackness::Front,
-                        roundedness: VowelRoundedness::Unrounded,
-                    }),
-                    diacritics: None,
-                } | Letter {
-                    ipa_type: LetterType::Vowel(Vowel {
-                        height: VowelHeight::OpenMid,
-                        backness: VowelBackness::Back,
-                        roundedness: VowelRoundedness::Rounded,
-                    }),
-                    diacritics: None,
-                },
-                Letter {
-                    ipa_type: LetterType::Vowel(Vowel {
-                        height: VowelHeight::NearClose,
-                        backness: VowelBackness::Front,
-                        roundedness: VowelRoundedness::Unrounded,
-                    }),
-                    diacritics: None,
-                },
-            ) | (
-                Letter {
-                    ipa_type: LetterType::Vowel(Vowel {
-                        height: VowelHeight::Mid,
-                        backness: VowelBackness::Central,
-                        roundedness: VowelRoundedness::Unrounded,
-                    }),
-                    diacritics: None,
-                } | Letter {
-                    ipa_type: LetterType::Vowel(Vowel {
-                        height: VowelHeight::CloseMid,
-                        backness: VowelBackness::Back,
-                        roundedness: VowelRoundedness::Rounded,
-                    }),
-                    diacritics: None,
-                } | Letter {
-                    ipa_type: LetterType::Vowel(Vowel {
-                        height: VowelHeight::Open,
-                        backness: VowelBackness::Front,
-                        roundedness: VowelRoundedness::Unrounded,
-                    }),
-                    diacritics: None,
-                },
-                Letter {
-                    ipa_type: LetterType::Vowel(Vowel {
-                        height: VowelHeight::NearClose,
-                        backness: VowelBackness::Back,
-                        roundedness: VowelRoundedness::Rounded,
-                    }),
-                    diacritics: None,
-                },
-            ) | (
-                // own, not included in wikipedia
-                Letter {
-                    ipa_type: LetterType::Vowel(Vowel {
-                        height: VowelHeight::OpenMid,
-                        backness: VowelBackness::Front,
-                        roundedness: VowelRoundedness::Unrounded,
-                    }),
-                    diacritics: None,
-                },
-                Letter {
-                    ipa_type: LetterType::Vowel(Vowel {
-                        height: VowelHeight::Mid,
-                        backness: VowelBackness::Central,
-                        roundedness: VowelRoundedness::Unrounded,
-                    }),
-                    diacritics: None,
-                }
+            c.manner,
+            PulmonicConsonantManner::SibilantFricative
+                | PulmonicConsonantManner::NonSibilantFricative
+        )
+    })
+}
+
+fn is_labial(letter: &Letter) -> bool {
+    pulmonic(letter).is_some_and(|c| {
+        matches!(
+            c.place,
+            ConsonantPlace::Bilabial | ConsonantPlace::Labiodental
+        )
+    })
+}
+
+fn is_coronal(letter: &Letter) -> bool {
+    pulmonic(letter)
+        .is_some_and(|c| matches!(c.place, ConsonantPlace::Dental | ConsonantPlace::Aveolar))
+}
+
+fn is_lateral(letter: &Letter) -> bool {
+    matches!(
+        letter.ipa_type,
+        LetterType::PulmonicConsonant(PulmonicConsonant {
+            manner: PulmonicConsonantManner::LateralApproximant,
+            ..
+        }) | LetterType::MiscLetter(
+            MiscLetter::VelarizedAveolar | MiscLetter::VoicedAlveolarLateralApproximant
+        )
+    )
+}
+
+fn is_rhotic(letter: &Letter) -> bool {
+    pulmonic(letter).is_some_and(|c| {
+        c.manner == PulmonicConsonantManner::Approximant
+            && matches!(
+                c.place,
+                ConsonantPlace::Aveolar | ConsonantPlace::Postalveolar | ConsonantPlace::Retroflex
             )
+    })
+}
+
+// non-syllabic i and u are written for j and w too
+fn is_glide(letter: &Letter, backness: VowelBackness, roundedness: VowelRoundedness) -> bool {
+    letter.has_diacritic(Diacritic::NonSyllabic)
+        && letter.ipa_type
+            == LetterType::Vowel(Vowel {
+                height: VowelHeight::Close,
+                backness,
+                roundedness,
+            })
+}
+
+fn is_yod(letter: &Letter) -> bool {
+    pulmonic(letter).is_some_and(|c| {
+        c.manner == PulmonicConsonantManner::Approximant && c.place == ConsonantPlace::Palatal
+    }) || is_glide(letter, VowelBackness::Front, VowelRoundedness::Unrounded)
+}
+
+fn is_w(letter: &Letter) -> bool {
+    matches!(
+        letter.ipa_type,
+        LetterType::MiscLetter(
+            MiscLetter::VoicedLabialPalatalApproximant
+                | MiscLetter::VoicelessLabialPalatalApproximant
+                | MiscLetter::VoicedLabialVelarApproximant
+                | MiscLetter::VoicelessLabialVelarApproximant
         )
+    ) || is_glide(letter, VowelBackness::Back, VowelRoundedness::Rounded)
+}
+
+fn is_valid_pair(first: &Letter, second: &Letter) -> bool {
+    // s-clusters are the only exception to rising sonority
+    if is_s(first) {
+        return is_voiceless_plosive(second)
+            || (is_nasal(second) && !is_ng(second))
+            || is_lateral(second)
+            || is_w(second)
+            || is_yod(second)
+            || (is_fricative(second) && is_labial(second));
+    }
+    match (first.sonority(), second.sonority()) {
+        (Some(a), Some(b)) if a < b => {}
+        _ => return false,
+    }
+    if is_sh(first) {
+        // shr, and schl, schm, schn, schw from loans
+        return is_rhotic(second) || is_lateral(second) || is_nasal(second) || is_w(second);
+    }
+    if is_h(first) {
+        return is_yod(second) || is_w(second);
+    }
+    let Some(consonant) = pulmonic(first) else {
+        return false;
+    };
+    match (&consonant.manner, &consonant.voicing) {
+        (PulmonicConsonantManner::Plosive, _)
+        | (
+            PulmonicConsonantManner::SibilantFricative
+            | PulmonicConsonantManner::NonSibilantFricative,
+            ConsonantVoicing::Voiceless,
+        ) => {
+            // no tl, dl, θl and no pw, bw, fw
+            (is_lateral(second) && !is_coronal(first))
+                || is_rhotic(second)
+                || (is_w(second) && !is_labial(first))
+                || is_yod(second)
+        }
+        // voiced fricatives and nasals only before j, like in view or music
+        _ => is_yod(second),
+    }
+}
+
+fn is_unmarked_syllabic(word: &[Letter], index: usize) -> bool {
+    let end = word
+        .iter()
+        .rposition(|x| x.sonority().is_some())
+        .unwrap_or(0);
+    if index != end || index == 0 {
+        return false;
+    }
+    let letter = &word[index];
+    let sonorant = (is_nasal(letter) && !is_ng(letter)) || is_lateral(letter);
+    // only directly after an obstruent, so film or kiln stay one syllable
+    sonorant && word[index - 1].sonority().is_some_and(|x| x < 4)
+}
+
+impl SyllableRule for EnglishSyllableRule {
+    fn makes_valid_onset(&self, new: &Letter, rest: &[Letter]) -> bool {
+        if new.sonority().is_none() {
+            return true;
+        }
+        let onset = onset_with(new, rest);
+        if onset.iter().any(|x| is_ng(x)) {
+            return false;
+        }
+        match onset.as_slice() {
+            [_] => true,
+            [first, second] => is_valid_pair(first, second),
+            [first, second, third] => {
+                is_s(first) && is_voiceless_plosive(second) && is_valid_pair(second, third)
+            }
+            _ => false,
+        }
+    }
+    fn is_syllabic_consonant(&self, word: &[Letter], index: usize) -> bool {
+        word[index].is_syllabic() || (self.unmarked_syllabics && is_unmarked_syllabic(word, index))
+    }
+    fn inventory(&self) -> Option<&PhonemeInventory> {
+        Some(self.dialect.inventory())
+    }
+
+    fn is_diphthong(&self, nucleus: &[Letter]) -> bool {
+        self.dialect.inventory().is_diphthong(nucleus)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn syllabify(input: &str) -> Vec<String> {
+        syls_from_word(
+            &Word::try_from(input).unwrap(),
+            &EnglishSyllableRule::default(),
+        )
+        .iter()
+        .map(|x| x.to_string())
+        .collect()
+    }
+
+    #[test]
+    fn maximal_onset() {
+        assert_eq!(syllabify("ˈɛkstɹə"), ["ˈɛk", "stɹə"]);
+        assert_eq!(syllabify("ˈhæmstɝ"), ["ˈhæm", "stɜ˞"]);
+        assert_eq!(syllabify("ˈʌpɹaɪt"), ["ˈʌ", "pɹaɪt"]);
+    }
+
+    #[test]
+    fn banned_onsets() {
+        assert_eq!(syllabify("ˈsɪŋɚ"), ["ˈsɪŋ", "ə˞"]);
+        assert_eq!(syllabify("ˈætləs"), ["ˈæt", "ləs"]);
+        assert_eq!(syllabify("ˈæθlit"), ["ˈæθ", "lit"]);
+        assert_eq!(syllabify("ˈpæknɪk"), ["ˈpæk", "nɪk"]);
+    }
+
+    #[test]
+    fn single_consonant_codas() {
+        assert_eq!(syllabify("ˈsɪŋz"), ["ˈsɪŋz"]);
+        assert_eq!(syllabify("ˈstɹɛŋθs"), ["ˈstɹɛŋθs"]);
+    }
+
+    #[test]
+    fn syllabic_consonants() {
+        assert_eq!(syllabify("ˈbʌtn̩"), ["ˈbʌ", "tn\u{30D}"]);
+        assert_eq!(syllabify("ˈbɑtl̩"), ["ˈbɑ", "tl\u{30D}"]);
+        assert_eq!(syllabify("ˈbʌtn̩ɪŋ"), ["ˈbʌ", "tn\u{30D}", "ɪŋ"]);
+        // unmarked ones are only split when asked to
+        assert_eq!(syllabify("ˈbʌtn"), ["ˈbʌtn"]);
+        let rule = EnglishSyllableRule {
+            unmarked_syllabics: true,
+            ..Default::default()
+        };
+        let syls = |x| -> Vec<String> {
+            syls_from_word(&Word::try_from(x).unwrap(), &rule)
+                .iter()
+                .map(|x| x.to_string())
+                .collect()
+        };
+        assert_eq!(syls("ˈbʌtn"), ["ˈbʌ", "tn"]);
+        assert_eq!(syls("ˈpɹɪzm"), ["ˈpɹɪ", "zm"]);
+        assert_eq!(syls("fɪlm"), ["fɪlm"]);
+        assert_eq!(syls("ˈbʌtnz"), ["ˈbʌtnz"]);
+    }
+
+    #[test]
+    fn dialect_diphthongs() {
+        let syls = |x, dialect| -> Vec<Syllable> {
+            let rule = EnglishSyllableRule {
+                dialect,
+                ..Default::default()
+            };
+            syls_from_word(&Word::try_from(x).unwrap(), &rule)
+        };
+        let general = EnglishDialect::GeneralAmerican;
+        let received = EnglishDialect::ReceivedPronunciation;
+
+        let here = syls("ˈhɪɹ", general);
+        assert_eq!(here.len(), 1);
+        assert_eq!(here[0].nucleus.len(), 2);
+        assert!(here[0].coda.is_empty());
+        // the ɹ is still the onset of the next syllable
+        assert_eq!(syls("ˈhɪɹoʊ", general)[1].to_string(), "ɹoʊ");
+        assert_eq!(syls("ˈðɛɚ", general).len(), 1);
+
+        assert_eq!(syls("ˈhɪə", received).len(), 1);
+        assert_eq!(syls("ˈhɪə", general).len(), 2);
+        assert_eq!(syls("ˈfaɪə", received).len(), 1);
+        assert_eq!(
+            syls("ˈhɪə", EnglishDialect::AfricanAmericanVernacular).len(),
+            1
+        );
+    }
+
+    #[test]
+    fn diphthongs_before_r_coloured_vowels() {
+        // aɪ and ɪɚ are both diphthongs, but aɪɚ isn't one
+        assert_eq!(syllabify("ˈfaɪɚ"), ["ˈfaɪ", "ə˞"]);
+        assert_eq!(syllabify("ˈlaɪɚ"), ["ˈlaɪ", "ə˞"]);
+        assert_eq!(syllabify("ˈpleɪɚ"), ["ˈpleɪ", "ə˞"]);
+        assert_eq!(syllabify("ˈpaʊɚ"), ["ˈpaʊ", "ə˞"]);
+        assert_eq!(syllabify("ˈnɪɚ"), ["ˈnɪə˞"]);
+    }
+
+    #[test]
+    fn non_syllabic_glides() {
+        assert_eq!(syllabify("ˈhaɪ̯"), ["ˈhaɪ\u{0311}"]);
+        // not in the table, but marked as a glide
+        assert_eq!(syllabify("ˈhɛɪ̯"), ["ˈhɛɪ\u{0311}"]);
+        assert_eq!(syllabify("ˈku̯ɪk"), ["ˈku\u{0311}ɪk"]);
     }
 }
@@ -0,0 +1,242 @@
+use crate::ipa_utils::ipa::*;
+use anyhow::{anyhow, Context};
+use std::collections::HashMap;
+
+// measures a syllable rule against a gold file of syllabified words
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GoldEntry {
+    pub label: String,
+    // without the syllable breaks, stress marks are kept
+    pub word: Word,
+    // indices of the segments that start a syllable, except the first one
+    pub breaks: Vec<usize>,
+}
+
+impl GoldEntry {
+    // syllables are separated by syllable breaks or stress marks, like ˈsɪ.ti
+    pub fn parse(label: &str, syllabified: &str) -> Result<Self, IpaParseError> {
+        let mut letters = vec![];
+        let mut breaks = vec![];
+        let mut segments = 0;
+        for letter in Word::try_from(syllabified)?.0 {
+            match letter.ipa_type {
+                LetterType::Suprasegmental(
+                    Suprasegmental::SyllableBreak
+                    | Suprasegmental::PrimaryStress
+                    | Suprasegmental::SecondaryStress,
+                ) if segments > 0 && breaks.last() != Some(&segments) => breaks.push(segments),
+                _ if letter.sonority().is_some() => segments += 1,
+                _ => {}
+            }
+            if letter.ipa_type != LetterType::Suprasegmental(Suprasegmental::SyllableBreak) {
+                letters.push(letter);
+            }
+        }
+        breaks.retain(|x| *x < segments);
+        Ok(Self {
+            label: label.to_string(),
+            word: Word(letters),
+            breaks,
+        })
+    }
+}
+
+// one word per line, the label and the syllabified ipa separated by a tab, # starts a comment
+pub fn parse_gold(input: &str) -> anyhow::Result<Vec<GoldEntry>> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .map(|(number, line)| {
+            let (label, ipa) = line
+                .split_once('\t')
+                .ok_or_else(|| anyhow!("line {number}: no tab between word and ipa"))?;
+            GoldEntry::parse(label.trim(), ipa.trim()).with_context(|| format!("line {number}"))
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Failure {
+    pub label: String,
+    pub expected: String,
+    pub predicted: String,
+    // the consonants around every misplaced break, vowels are written as V
+    pub patterns: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Evaluation {
+    // labels of the words that were syllabified right
+    pub correct: Vec<String>,
+    pub failures: Vec<Failure>,
+}
+
+impl Evaluation {
+    pub fn new(gold: &[GoldEntry], rule: &dyn SyllableRule) -> Self {
+        let mut out = Self::default();
+        for entry in gold {
+            let predicted = predicted_breaks(&syls_from_word(&entry.word, rule));
+            if predicted == entry.breaks {
+                out.correct.push(entry.label.clone());
+                continue;
+            }
+            let syllabic: Vec<bool> = (0..entry.word.0.len())
+                .filter(|i| entry.word.0[*i].sonority().is_some())
+                .map(|i| rule.is_syllabic(&entry.word.0, i))
+                .collect();
+            out.failures.push(Failure {
+                label: entry.label.clone(),
+                expected: render(&entry.word, &entry.breaks),
+                predicted: render(&entry.word, &predicted),
+                patterns: patterns(&entry.word, &syllabic, &entry.breaks, &predicted),
+            });
+        }
+        out
+    }
+
+    pub fn total(&self) -> usize {
+        self.correct.len() + self.failures.len()
+    }
+
+    pub fn accuracy(&self) -> f64 {
+        if self.total() == 0 {
+            return 0.0;
+        }
+        self.correct.len() as f64 / self.total() as f64
+    }
+
+    // every pattern with the number of words it appears in, the most common first
+    pub fn error_patterns(&self) -> Vec<(&str, usize)> {
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        for pattern in self.failures.iter().flat_map(|x| &x.patterns) {
+            *counts.entry(pattern).or_default() += 1;
+        }
+        let mut out: Vec<(&str, usize)> = counts.into_iter().collect();
+        out.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        out
+    }
+
+    // failures of words that were right in the baseline
+    pub fn regressions(&self, baseline: &[String]) -> Vec<&Failure> {
+        self.failures
+            .iter()
+            .filter(|x| baseline.contains(&x.label))
+            .collect()
+    }
+}
+
+fn segments(letters: &[Letter]) -> usize {
+    letters.iter().filter(|x| x.sonority().is_some()).count()
+}
+
+fn predicted_breaks(syls: &[Syllable]) -> Vec<usize> {
+    let mut out = vec![];
+    let mut count = 0;
+    for syl in &syls[..syls.len().saturating_sub(1)] {
+        count += segments(&syl.onset) + segments(&syl.nucleus) + segments(&syl.coda);
+        out.push(count);
+    }
+    out
+}
+
+// stress marks already show the break in front of them
+fn render(word: &Word, breaks: &[usize]) -> String {
+    let mut out = String::new();
+    let mut segment = 0;
+    let mut after_stress = false;
+    for letter in &word.0 {
+        if letter.sonority().is_some() {
+            if breaks.contains(&segment) && !after_stress {
+                out.push('.');
+            }
+            segment += 1;
+        }
+        after_stress = matches!(
+            letter.ipa_type,
+            LetterType::Suprasegmental(
+                Suprasegmental::PrimaryStress | Suprasegmental::SecondaryStress
+            )
+        );
+        out += &letter.to_string();
+    }
+    out
+}
+
+fn patterns(
+    word: &Word,
+    syllabic: &[bool],
+    expected: &[usize],
+    predicted: &[usize],
+) -> Vec<String> {
+    let segments: Vec<&Letter> = word.0.iter().filter(|x| x.sonority().is_some()).collect();
+    let cluster = |start: usize, end: usize, breaks: &[usize]| {
+        let mut out = String::new();
+        for i in start..end {
+            if i != start && breaks.contains(&i) {
+                out.push('.');
+            }
+            if syllabic[i] {
+                out.push('V');
+            } else {
+                out += &segments[i].to_string();
+            }
+        }
+        out
+    };
+    let misplaced = expected
+        .iter()
+        .filter(|x| !predicted.contains(x))
+        .chain(predicted.iter().filter(|x| !expected.contains(x)));
+    let mut out = vec![];
+    for &at in misplaced {
+        // from the nucleus before the break to the one after it
+        let start = (0..at).rev().find(|i| syllabic[*i]).unwrap_or(0);
+        let end = (at..segments.len())
+            .find(|i| syllabic[*i])
+            .map_or(segments.len(), |i| i + 1);
+        let pattern = format!(
+            "{} -> {}",
+            cluster(start, end, expected),
+            cluster(start, end, predicted)
+        );
+        if !out.contains(&pattern) {
+            out.push(pattern);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ipa_utils::ipa::english::EnglishSyllableRule;
+
+    #[test]
+    fn gold_breaks() {
+        let entry = GoldEntry::parse("extra", "/ˈɛk.stɹə/").unwrap();
+        assert_eq!(entry.breaks, [2]);
+        assert_eq!(entry.word.to_string(), "ˈɛkstɹə");
+        let entry = GoldEntry::parse("banana", "bə.ˈnæ.nə").unwrap();
+        assert_eq!(entry.breaks, [2, 4]);
+        assert!(parse_gold("city ˈsɪ.ti").is_err());
+    }
+
+    #[test]
+    fn failures_and_patterns() {
+        let gold = parse_gold("# comment\nextra\tˈɛks.tɹə\n\nsinger\tˈsɪŋ.ɚ\n").unwrap();
+        let evaluation = Evaluation::new(&gold, &EnglishSyllableRule::default());
+        assert_eq!(evaluation.total(), 2);
+        assert_eq!(evaluation.correct, ["singer"]);
+        assert_eq!(evaluation.accuracy(), 0.5);
+        let failure = &evaluation.failures[0];
+        assert_eq!(failure.expected, "ˈɛks.tɹə");
+        assert_eq!(failure.predicted, "ˈɛk.stɹə");
+        assert_eq!(failure.patterns, ["Vks.tɹV -> Vk.stɹV"]);
+        assert_eq!(evaluation.error_patterns(), [("Vks.tɹV -> Vk.stɹV", 1)]);
+        assert_eq!(evaluation.regressions(&["extra".to_string()]).len(), 1);
+        assert!(evaluation.regressions(&["singer".to_string()]).is_empty());
+    }
+}
@@ -0,0 +1,615 @@
+use crate::ipa_utils::ipa::*;
+
+// feature set loosely following Hayes, Introductory Phonology (2009)
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Feature {
+    Syllabic,
+    Consonantal,
+    Approximant,
+    Sonorant,
+    Continuant,
+    DelayedRelease,
+    Nasal,
+    Lateral,
+    Strident,
+    Tap,
+    Trill,
+    Voice,
+    SpreadGlottis,
+    ConstrictedGlottis,
+    Labial,
+    Round,
+    Labiodental,
+    Coronal,
+    Anterior,
+    Distributed,
+    Dorsal,
+    High,
+    Low,
+    Front,
+    Back,
+    Tense,
+}
+
+const FEATURE_LIST: [(Feature, &str); 26] = [
+    (Feature::Syllabic, "syllabic"),
+    (Feature::Consonantal, "consonantal"),
+    (Feature::Approximant, "approximant"),
+    (Feature::Sonorant, "sonorant"),
+    (Feature::Continuant, "continuant"),
+    (Feature::DelayedRelease, "delayed release"),
+    (Feature::Nasal, "nasal"),
+    (Feature::Lateral, "lateral"),
+    (Feature::Strident, "strident"),
+    (Feature::Tap, "tap"),
+    (Feature::Trill, "trill"),
+    (Feature::Voice, "voice"),
+    (Feature::SpreadGlottis, "spread glottis"),
+    (Feature::ConstrictedGlottis, "constricted glottis"),
+    (Feature::Labial, "labial"),
+    (Feature::Round, "round"),
+    (Feature::Labiodental, "labiodental"),
+    (Feature::Coronal, "coronal"),
+    (Feature::Anterior, "anterior"),
+    (Feature::Distributed, "distributed"),
+    (Feature::Dorsal, "dorsal"),
+    (Feature::High, "high"),
+    (Feature::Low, "low"),
+    (Feature::Front, "front"),
+    (Feature::Back, "back"),
+    (Feature::Tense, "tense"),
+];
+
+impl fmt::Display for Feature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (_, name) = FEATURE_LIST[*self as usize];
+        write!(f, "{name}")
+    }
+}
+
+impl TryFrom<&str> for Feature {
+    type Error = ();
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        FEATURE_LIST
+            .iter()
+            .find(|(_, name)| *name == value)
+            .map(|(feature, _)| *feature)
+            .ok_or(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FeatureValue {
+    Plus,
+    Minus,
+    // the feature doesn't apply, like anterior for a velar
+    #[default]
+    Unspecified,
+}
+
+impl From<bool> for FeatureValue {
+    fn from(value: bool) -> Self {
+        if value {
+            FeatureValue::Plus
+        } else {
+            FeatureValue::Minus
+        }
+    }
+}
+
+impl fmt::Display for FeatureValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let a = match self {
+            FeatureValue::Plus => "+",
+            FeatureValue::Minus => "-",
+            FeatureValue::Unspecified => "0",
+        };
+        write!(f, "{a}")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FeatureVector([FeatureValue; FEATURE_LIST.len()]);
+
+impl FeatureVector {
+    pub fn get(&self, feature: Feature) -> FeatureValue {
+        self.0[feature as usize]
+    }
+
+    pub fn set(&mut self, feature: Feature, value: impl Into<FeatureValue>) {
+        self.0[feature as usize] = value.into();
+    }
+
+    fn set_all(&mut self, features: &[Feature], value: bool) {
+        for feature in features {
+            self.set(*feature, value);
+        }
+    }
+
+    // a natural class like voiced obstruents is [(Sonorant, Minus), (Voice, Plus)]
+    pub fn matches(&self, class: &[(Feature, FeatureValue)]) -> bool {
+        class
+            .iter()
+            .all(|(feature, value)| self.get(*feature) == *value)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Feature, FeatureValue)> + '_ {
+        FEATURE_LIST
+            .iter()
+            .map(|(feature, _)| (*feature, self.get(*feature)))
+    }
+}
+
+impl fmt::Display for FeatureVector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let a: Vec<String> = self
+            .iter()
+            .filter(|(_, value)| *value != FeatureValue::Unspecified)
+            .map(|(feature, value)| format!("{value}{feature}"))
+            .collect();
+        write!(f, "[{}]", a.join(", "))
+    }
+}
+
+// parses classes written like "+voice -sonorant"
+pub fn natural_class(value: &str) -> Result<Vec<(Feature, FeatureValue)>, anyhow::Error> {
+    value
+        .split_whitespace()
+        .map(|x| {
+            let (value, name) = match x.split_at(x.chars().next().map_or(0, |c| c.len_utf8())) {
+                ("+", name) => (FeatureValue::Plus, name),
+                ("-", name) => (FeatureValue::Minus, name),
+                ("0", name) => (FeatureValue::Unspecified, name),
+                _ => return Err(anyhow!("feature {x} has no value")),
+            };
+            let name = name.replace('_', " ");
+            let feature =
+                Feature::try_from(name.as_str()).map_err(|_| anyhow!("unknown feature {name}"))?;
+            Ok((feature, value))
+        })
+        .collect()
+}
+
+const PLACE_FEATURES: [Feature; 11] = [
+    Feature::Labial,
+    Feature::Round,
+    Feature::Labiodental,
+    Feature::Coronal,
+    Feature::Anterior,
+    Feature::Distributed,
+    Feature::Dorsal,
+    Feature::High,
+    Feature::Low,
+    Feature::Front,
+    Feature::Back,
+];
+
+fn place_features(features: &mut FeatureVector, place: &ConsonantPlace) {
+    for feature in PLACE_FEATURES {
+        features.set(feature, FeatureValue::Unspecified);
+    }
+    features.set_all(&[Feature::Labial, Feature::Coronal, Feature::Dorsal], false);
+    match place {
+        ConsonantPlace::Bilabial => {
+            features.set(Feature::Labial, true);
+            features.set_all(&[Feature::Round, Feature::Labiodental], false);
+        }
+        ConsonantPlace::Labiodental => {
+            features.set_all(&[Feature::Labial, Feature::Labiodental], true);
+            features.set(Feature::Round, false);
+        }
+        ConsonantPlace::Linguolabial => {
+            features.set_all(
+                &[Feature::Labial, Feature::Coronal, Feature::Anterior],
+                true,
+            );
+            features.set_all(&[Feature::Round, Feature::Labiodental], false);
+            features.set(Feature::Distributed, false);
+        }
+        ConsonantPlace::Dental => {
+            features.set_all(
+                &[Feature::Coronal, Feature::Anterior, Feature::Distributed],
+                true,
+            );
+        }
+        ConsonantPlace::Aveolar => {
+            features.set_all(&[Feature::Coronal, Feature::Anterior], true);
+            features.set(Feature::Distributed, false);
+        }
+        ConsonantPlace::Postalveolar => {
+            features.set_all(&[Feature::Coronal, Feature::Distributed], true);
+            features.set(Feature::Anterior, false);
+        }
+        ConsonantPlace::Retroflex => {
+            features.set(Feature::Coronal, true);
+            features.set_all(&[Feature::Anterior, Feature::Distributed], false);
+        }
+        ConsonantPlace::Palatal => {
+            features.set_all(&[Feature::Dorsal, Feature::High, Feature::Front], true);
+            features.set_all(&[Feature::Low, Feature::Back], false);
+        }
+        ConsonantPlace::Velar => {
+            features.set_all(&[Feature::Dorsal, Feature::High, Feature::Back], true);
+            features.set_all(&[Feature::Low, Feature::Front], false);
+        }
+        ConsonantPlace::Uvular => {
+            features.set_all(&[Feature::Dorsal, Feature::Back], true);
+            features.set_all(&[Feature::High, Feature::Low, Feature::Front], false);
+        }
+        ConsonantPlace::Pharyngeal => {
+            features.set_all(&[Feature::Low, Feature::Back], true);
+        }
+        ConsonantPlace::Glottal => {}
+    }
+}
+
+fn manner_features(features: &mut FeatureVector, manner: &PulmonicConsonantManner) {
+    use PulmonicConsonantManner as M;
+    features.set(Feature::Syllabic, false);
+    features.set(Feature::Consonantal, true);
+    features.set(
+        Feature::Sonorant,
+        matches!(
+            manner,
+            M::Nasal | M::Approximant | M::Tap | M::Trill | M::LateralApproximant | M::LateralTap
+        ),
+    );
+    features.set(
+        Feature::Approximant,
+        matches!(
+            manner,
+            M::Approximant | M::Tap | M::Trill | M::LateralApproximant | M::LateralTap
+        ),
+    );
+    features.set(
+        Feature::Continuant,
+        !matches!(manner, M::Nasal | M::Plosive | M::Tap | M::LateralTap),
+    );
+    features.set(
+        Feature::DelayedRelease,
+        matches!(
+            manner,
+            M::SibilantFricative | M::NonSibilantFricative | M::LateralFricative
+        ),
+    );
+    features.set(Feature::Nasal, *manner == M::Nasal);
+    features.set(
+        Feature::Lateral,
+        matches!(
+            manner,
+            M::LateralFricative | M::LateralApproximant | M::LateralTap
+        ),
+    );
+    features.set(Feature::Strident, *manner == M::SibilantFricative);
+    features.set(Feature::Tap, matches!(manner, M::Tap | M::LateralTap));
+    features.set(Feature::Trill, *manner == M::Trill);
+    features.set_all(
+        &[Feature::SpreadGlottis, Feature::ConstrictedGlottis],
+        false,
+    );
+}
+
+impl PulmonicConsonant {
+    pub fn features(&self) -> FeatureVector {
+        let mut features = FeatureVector::default();
+        manner_features(&mut features, &self.manner);
+        place_features(&mut features, &self.place);
+        features.set(Feature::Voice, self.voicing == ConsonantVoicing::Voiced);
+        match (&self.manner, &self.place) {
+            // glides like j and ɰ are vowels in all but syllabicity
+            (PulmonicConsonantManner::Approximant, place)
+                if !matches!(
+                    place,
+                    ConsonantPlace::Aveolar
+                        | ConsonantPlace::Postalveolar
+                        | ConsonantPlace::Retroflex
+                ) =>
+            {
+                features.set(Feature::Consonantal, false);
+            }
+            (PulmonicConsonantManner::Plosive, ConsonantPlace::Glottal) => {
+                features.set(Feature::Consonantal, false);
+                features.set(Feature::ConstrictedGlottis, true);
+            }
+            (_, ConsonantPlace::Glottal) => {
+                features.set(Feature::Consonantal, false);
+                features.set(Feature::SpreadGlottis, true);
+            }
+            _ => {}
+        }
+        features
+    }
+}
+
+impl NonPulmonicConsonant {
+    pub fn features(&self) -> FeatureVector {
+        let mut features = FeatureVector::default();
+        let manner = match &self.kind {
+            NonPulmonicConsonantKind::Click => PulmonicConsonantManner::Plosive,
+            NonPulmonicConsonantKind::LateralClick => PulmonicConsonantManner::LateralFricative,
+            NonPulmonicConsonantKind::Implosive => PulmonicConsonantManner::Plosive,
+            NonPulmonicConsonantKind::Ejective(manner) => manner.clone(),
+        };
+        manner_features(&mut features, &manner);
+        place_features(&mut features, &self.place);
+        match self.kind {
+            NonPulmonicConsonantKind::Click | NonPulmonicConsonantKind::LateralClick => {
+                // clicks have a velar or uvular back closure
+                features.set_all(&[Feature::Dorsal, Feature::Back], true);
+                features.set(Feature::Continuant, false);
+                features.set(Feature::Voice, false);
+            }
+            NonPulmonicConsonantKind::Implosive => {
+                features.set_all(&[Feature::Voice, Feature::ConstrictedGlottis], true);
+            }
+            NonPulmonicConsonantKind::Ejective(_) => {
+                features.set(Feature::Voice, false);
+                features.set(Feature::ConstrictedGlottis, true);
+            }
+        }
+        features
+    }
+}
+
+impl Vowel {
+    pub fn features(&self) -> FeatureVector {
+        let mut features = FeatureVector::default();
+        features.set_all(
+            &[
+                Feature::Syllabic,
+                Feature::Approximant,
+                Feature::Sonorant,
+                Feature::Continuant,
+                Feature::Voice,
+                Feature::Dorsal,
+            ],
+            true,
+        );
+        features.set_all(
+            &[
+                Feature::Consonantal,
+                Feature::DelayedRelease,
+                Feature::Nasal,
+                Feature::Lateral,
+                Feature::Strident,
+                Feature::Tap,
+                Feature::Trill,
+                Feature::SpreadGlottis,
+                Feature::ConstrictedGlottis,
+                Feature::Labiodental,
+                Feature::Coronal,
+            ],
+            false,
+        );
+        let rounded = self.roundedness == VowelRoundedness::Rounded;
+        features.set(Feature::Labial, rounded);
+        features.set(Feature::Round, rounded);
+        features.set(
+            Feature::High,
+            matches!(self.height, VowelHeight::Close | VowelHeight::NearClose),
+        );
+        features.set(
+            Feature::Low,
+            matches!(self.height, VowelHeight::Open | VowelHeight::NearOpen),
+        );
+        features.set(Feature::Front, self.backness == VowelBackness::Front);
+        features.set(Feature::Back, self.backness == VowelBackness::Back);
+        match self.height {
+            VowelHeight::Close | VowelHeight::CloseMid => features.set(Feature::Tense, true),
+            VowelHeight::NearClose | VowelHeight::OpenMid | VowelHeight::NearOpen => {
+                features.set(Feature::Tense, false)
+            }
+            VowelHeight::Mid | VowelHeight::Open => {}
+        }
+        features
+    }
+}
+
+impl MiscLetter {
+    pub fn features(&self) -> FeatureVector {
+        let consonant = |manner, place, voicing| PulmonicConsonant {
+            manner,
+            place,
+            voicing,
+        };
+        let glide = |voicing, front: bool| {
+            let mut features = consonant(
+                PulmonicConsonantManner::Approximant,
+                ConsonantPlace::Velar,
+                voicing,
+            )
+            .features();
+            features.set_all(&[Feature::Labial, Feature::Round], true);
+            features.set(Feature::Labiodental, false);
+            features.set(Feature::Front, front);
+            features.set(Feature::Back, !front);
+            features
+        };
+        match self {
+            MiscLetter::VoicedLabialPalatalApproximant => glide(ConsonantVoicing::Voiced, true),
+            MiscLetter::VoicelessLabialPalatalApproximant => {
+                glide(ConsonantVoicing::Voiceless, true)
+            }
+            MiscLetter::VoicedLabialVelarApproximant => glide(ConsonantVoicing::Voiced, false),
+            MiscLetter::VoicelessLabialVelarApproximant => {
+                glide(ConsonantVoicing::Voiceless, false)
+            }
+            MiscLetter::SjSound => {
+                let mut features = consonant(
+                    PulmonicConsonantManner::NonSibilantFricative,
+                    ConsonantPlace::Postalveolar,
+                    ConsonantVoicing::Voiceless,
+                )
+                .features();
+                features.set_all(&[Feature::Dorsal, Feature::High, Feature::Back], true);
+                features
+            }
+            MiscLetter::VelarizedAveolar => {
+                let mut features = consonant(
+                    PulmonicConsonantManner::LateralApproximant,
+                    ConsonantPlace::Aveolar,
+                    ConsonantVoicing::Voiced,
+                )
+                .features();
+                features.set_all(&[Feature::Dorsal, Feature::High, Feature::Back], true);
+                features
+            }
+            MiscLetter::VoicedAlveolarLateralApproximant => consonant(
+                PulmonicConsonantManner::LateralApproximant,
+                ConsonantPlace::Aveolar,
+                ConsonantVoicing::Voiced,
+            )
+            .features(),
+        }
+    }
+}
+
+impl Diacritic {
+    pub fn apply(&self, features: &mut FeatureVector) {
+        match self {
+            Diacritic::Syllabic => features.set(Feature::Syllabic, true),
+            Diacritic::NonSyllabic => features.set(Feature::Syllabic, false),
+            Diacritic::Aspirated | Diacritic::BreathyVoiced => {
+                features.set(Feature::SpreadGlottis, true)
+            }
+            Diacritic::CreakyVoiced => features.set(Feature::ConstrictedGlottis, true),
+            Diacritic::Voiceless => features.set(Feature::Voice, false),
+            Diacritic::Voiced => features.set(Feature::Voice, true),
+            Diacritic::Dental => {
+                features.set_all(
+                    &[Feature::Coronal, Feature::Anterior, Feature::Distributed],
+                    true,
+                );
+            }
+            Diacritic::Apical => features.set(Feature::Distributed, false),
+            Diacritic::Laminal => features.set(Feature::Distributed, true),
+            Diacritic::MoreRounded | Diacritic::Labialized => {
+                features.set_all(&[Feature::Labial, Feature::Round], true)
+            }
+            Diacritic::LessRounded => features.set(Feature::Round, false),
+            Diacritic::Palatalized => {
+                features.set_all(&[Feature::Dorsal, Feature::High, Feature::Front], true);
+                features.set(Feature::Back, false);
+            }
+            Diacritic::Velarized => {
+                features.set_all(&[Feature::Dorsal, Feature::High, Feature::Back], true);
+                features.set(Feature::Front, false);
+            }
+            Diacritic::Pharyngealized | Diacritic::VelarizedOrPharyngealized => {
+                features.set(Feature::Back, true);
+                features.set(Feature::Front, false);
+            }
+            Diacritic::AdvancedTongueRoot => features.set(Feature::Tense, true),
+            Diacritic::RetractedTongueRoot => features.set(Feature::Tense, false),
+            Diacritic::Nasalized => features.set(Feature::Nasal, true),
+            // only change the phonetic detail, not the features
+            _ => {}
+        }
+    }
+}
+
+impl LetterType {
+    // suprasegmentals don't have any features
+    pub fn features(&self) -> Option<FeatureVector> {
+        match self {
+            LetterType::PulmonicConsonant(consonant) => Some(consonant.features()),
+            LetterType::NonPulmonicConsonant(consonant) => Some(consonant.features()),
+            LetterType::Vowel(vowel) => Some(vowel.features()),
+            LetterType::MiscLetter(misc) => Some(misc.features()),
+            LetterType::Affricate(affricate) => {
+                // a stop released into the fricative, the place is that of the fricative as the
+                // t in t͡ʃ is made further back than the one in t͡s
+                let mut features = affricate.second.features()?;
+                features.set(Feature::Continuant, false);
+                features.set(Feature::DelayedRelease, true);
+                Some(features)
+            }
+            LetterType::Suprasegmental(_) => None,
+        }
+    }
+}
+
+impl Letter {
+    pub fn features(&self) -> Option<FeatureVector> {
+        let mut features = self.ipa_type.features()?;
+        for diacritic in self.diacritics.iter().flatten() {
+            diacritic.apply(&mut features);
+        }
+        Some(features)
+    }
+
+    pub fn is_in_class(&self, class: &[(Feature, FeatureValue)]) -> bool {
+        self.features().is_some_and(|x| x.matches(class))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn features(input: &str) -> FeatureVector {
+        Letter::try_from(input).unwrap().features().unwrap()
+    }
+
+    #[test]
+    fn segment_features() {
+        let b = features("b");
+        assert_eq!(b.get(Feature::Voice), FeatureValue::Plus);
+        assert_eq!(b.get(Feature::Sonorant), FeatureValue::Minus);
+        assert_eq!(b.get(Feature::Labial), FeatureValue::Plus);
+        assert_eq!(b.get(Feature::Anterior), FeatureValue::Unspecified);
+
+        let u = features("u");
+        assert_eq!(u.get(Feature::Syllabic), FeatureValue::Plus);
+        assert_eq!(u.get(Feature::Round), FeatureValue::Plus);
+        assert_eq!(u.get(Feature::Back), FeatureValue::Plus);
+
+        let word = Word::try_from("t͡ʃ").unwrap();
+        let t = word.0[0].features().unwrap();
+        assert_eq!(t.get(Feature::DelayedRelease), FeatureValue::Plus);
+        assert_eq!(t.get(Feature::Strident), FeatureValue::Plus);
+        assert_eq!(t.get(Feature::Continuant), FeatureValue::Minus);
+        assert_eq!(t.get(Feature::Anterior), FeatureValue::Minus);
+        let ts = Word::try_from("t͡s").unwrap().0[0].features().unwrap();
+        assert_ne!(t, ts);
+        assert_eq!(ts.get(Feature::Anterior), FeatureValue::Plus);
+    }
+
+    #[test]
+    fn diacritic_features() {
+        assert_eq!(features("n̥").get(Feature::Voice), FeatureValue::Minus);
+        assert_eq!(
+            features("pʰ").get(Feature::SpreadGlottis),
+            FeatureValue::Plus
+        );
+        assert_eq!(features("n̩").get(Feature::Syllabic), FeatureValue::Plus);
+        assert_eq!(
+            features("a\u{0303}").get(Feature::Nasal),
+            FeatureValue::Plus
+        );
+    }
+
+    #[test]
+    fn natural_classes() {
+        let voiced_obstruents = natural_class("+voice -sonorant").unwrap();
+        let members: Vec<&str> = ["b", "p", "z", "s", "m", "a", "ɡ"]
+            .into_iter()
+            .filter(|x| {
+                Letter::try_from(*x)
+                    .unwrap()
+                    .is_in_class(&voiced_obstruents)
+            })
+            .collect();
+        assert_eq!(members, ["b", "z", "ɡ"]);
+        assert!(natural_class("+voice sonorant").is_err());
+        assert!(natural_class("+loud").is_err());
+        assert_eq!(
+            natural_class("-constricted_glottis").unwrap(),
+            [(Feature::ConstrictedGlottis, FeatureValue::Minus)]
+        );
+    }
+}
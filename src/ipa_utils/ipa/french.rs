@@ -0,0 +1,100 @@
+use crate::ipa_utils::ipa::inventory::ClusterList;
+use crate::ipa_utils::ipa::*;
+use std::sync::LazyLock;
+
+#[derive(Debug, Clone, Default)]
+pub struct FrenchSyllableRule {}
+
+// see https://en.wikipedia.org/wiki/French_phonology
+
+const FRENCH_CONSONANT_LIST: [&str; 21] = [
+    "p", "b", "t", "d", "k", "ɡ", "f", "v", "s", "z", "ʃ", "ʒ", "m", "n", "ɲ", "ŋ", "l", "ʁ", "j",
+    "w", "ɥ",
+];
+
+const FRENCH_VOWEL_LIST: [&str; 16] = [
+    "i", "y", "u", "e", "ø", "o", "ɛ", "œ", "ɔ", "ə", "a", "ɑ", "ɛ̃", "œ̃", "ɔ̃", "ɑ̃",
+];
+
+// the glides are consonants, so there are no diphthongs
+const FRENCH_DIPHTHONG_LIST: [&str; 0] = [];
+
+// an obstruent and a liquid, s clusters only start words and are split otherwise
+const FRENCH_ONSET_LIST: [&str; 15] = [
+    "pl", "pʁ", "bl", "bʁ", "tʁ", "dʁ", "kl", "kʁ", "ɡl", "ɡʁ", "fl", "fʁ", "vʁ", "ps", "pn",
+];
+
+const FRENCH_GLIDE_LIST: [&str; 3] = ["j", "w", "ɥ"];
+
+const FRENCH_BANNED_ONSET_LIST: [&str; 1] = ["ŋ"];
+
+static FRENCH_INVENTORY: LazyLock<PhonemeInventory> = LazyLock::new(|| {
+    PhonemeInventory::new(
+        &FRENCH_CONSONANT_LIST,
+        &FRENCH_VOWEL_LIST,
+        &FRENCH_DIPHTHONG_LIST,
+    )
+    .expect("french inventory is valid ipa")
+});
+static FRENCH_ONSETS: LazyLock<ClusterList> =
+    LazyLock::new(|| ClusterList::new(&FRENCH_ONSET_LIST).expect("french onsets are valid ipa"));
+static FRENCH_GLIDES: LazyLock<ClusterList> =
+    LazyLock::new(|| ClusterList::new(&FRENCH_GLIDE_LIST).expect("french glides are valid ipa"));
+static FRENCH_BANNED_ONSETS: LazyLock<ClusterList> = LazyLock::new(|| {
+    ClusterList::new(&FRENCH_BANNED_ONSET_LIST).expect("french onsets are valid ipa")
+});
+
+impl SyllableRule for FrenchSyllableRule {
+    fn makes_valid_onset(&self, new: &Letter, rest: &[Letter]) -> bool {
+        let mut onset = onset_with(new, rest);
+        // every onset can end in a glide, like in tʁwa or nɥi
+        if onset.len() > 1
+            && onset
+                .last()
+                .is_some_and(|x| x.is_glide() || FRENCH_GLIDES.contains(&[x]))
+        {
+            onset.pop();
+        }
+        match onset.as_slice() {
+            [] => true,
+            [_] => !FRENCH_BANNED_ONSETS.contains(&onset),
+            _ => FRENCH_ONSETS.contains(&onset),
+        }
+    }
+    fn is_diphthong(&self, nucleus: &[Letter]) -> bool {
+        FRENCH_INVENTORY.is_diphthong(nucleus)
+    }
+    fn inventory(&self) -> Option<&PhonemeInventory> {
+        Some(&FRENCH_INVENTORY)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn syllabify(input: &str) -> Vec<String> {
+        syls_from_word(
+            &Word::try_from(input).unwrap(),
+            &FrenchSyllableRule::default(),
+        )
+        .iter()
+        .map(|x| x.to_string())
+        .collect()
+    }
+
+    #[test]
+    fn onsets() {
+        assert_eq!(syllabify("pʁɔblɛm"), ["pʁɔ", "blɛm"]);
+        assert_eq!(syllabify("ɛkspʁɛ"), ["ɛks", "pʁɛ"]);
+        assert_eq!(syllabify("tʁwa"), ["tʁwa"]);
+        // s clusters at the start of a word stay in the first syllable
+        assert_eq!(syllabify("spɔʁ"), ["spɔʁ"]);
+    }
+
+    #[test]
+    fn vowels() {
+        assert_eq!(syllabify("ɑ̃fɑ̃"), ["ɑ\u{0303}", "fɑ\u{0303}"]);
+        assert_eq!(syllabify("ʃao"), ["ʃa", "o"]);
+    }
+}
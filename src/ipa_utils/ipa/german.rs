@@ -0,0 +1,95 @@
+use crate::ipa_utils::ipa::inventory::ClusterList;
+use crate::ipa_utils::ipa::*;
+use std::sync::LazyLock;
+
+#[derive(Debug, Clone, Default)]
+pub struct GermanSyllableRule {}
+
+// see https://en.wikipedia.org/wiki/Standard_German_phonology
+
+const GERMAN_CONSONANT_LIST: [&str; 27] = [
+    "p", "b", "t", "d", "k", "ɡ", "ʔ", "p͡f", "t͡s", "t͡ʃ", "d͡ʒ", "f", "v", "s", "z", "ʃ", "ʒ", "ç",
+    "x", "h", "m", "n", "ŋ", "l", "ʁ", "r", "j",
+];
+
+// length is marked separately
+const GERMAN_VOWEL_LIST: [&str; 15] = [
+    "i", "ɪ", "y", "ʏ", "e", "ɛ", "ø", "œ", "a", "ə", "ɐ", "o", "ɔ", "u", "ʊ",
+];
+
+const GERMAN_DIPHTHONG_LIST: [&str; 4] = ["aɪ", "aʊ", "ɔʏ", "ɔɪ"];
+
+// affricates are listed tied and untied, as dictionaries write both
+const GERMAN_ONSET_LIST: [&str; 46] = [
+    "pl", "pʁ", "bl", "bʁ", "tʁ", "dʁ", "kl", "kʁ", "kn", "kv", "ɡl", "ɡʁ", "ɡn", "fl", "fʁ", "vʁ",
+    "ʃl", "ʃʁ", "ʃm", "ʃn", "ʃv", "ʃp", "ʃt", "ʃpʁ", "ʃpl", "ʃtʁ", "sk", "skʁ", "sl", "sm", "sn",
+    "sp", "st", "ts", "tsv", "t͡sv", "pf", "pfl", "pfʁ", "p͡fl", "p͡fʁ", "ps", "ks", "tʃ", "dʒ", "sv",
+];
+
+const GERMAN_BANNED_ONSET_LIST: [&str; 1] = ["ŋ"];
+
+static GERMAN_INVENTORY: LazyLock<PhonemeInventory> = LazyLock::new(|| {
+    PhonemeInventory::new(
+        &GERMAN_CONSONANT_LIST,
+        &GERMAN_VOWEL_LIST,
+        &GERMAN_DIPHTHONG_LIST,
+    )
+    .expect("german inventory is valid ipa")
+});
+static GERMAN_ONSETS: LazyLock<ClusterList> =
+    LazyLock::new(|| ClusterList::new(&GERMAN_ONSET_LIST).expect("german onsets are valid ipa"));
+static GERMAN_BANNED_ONSETS: LazyLock<ClusterList> = LazyLock::new(|| {
+    ClusterList::new(&GERMAN_BANNED_ONSET_LIST).expect("german onsets are valid ipa")
+});
+
+impl SyllableRule for GermanSyllableRule {
+    fn makes_valid_onset(&self, new: &Letter, rest: &[Letter]) -> bool {
+        let mut onset = onset_with(new, rest);
+        // a glide in front of the nucleus, like the i̯ in Studium
+        if onset.len() > 1 && onset.last().is_some_and(|x| x.is_glide()) {
+            onset.pop();
+        }
+        match onset.as_slice() {
+            [] => true,
+            [_] => !GERMAN_BANNED_ONSETS.contains(&onset),
+            _ => GERMAN_ONSETS.contains(&onset),
+        }
+    }
+    fn is_diphthong(&self, nucleus: &[Letter]) -> bool {
+        GERMAN_INVENTORY.is_diphthong(nucleus)
+    }
+    fn inventory(&self) -> Option<&PhonemeInventory> {
+        Some(&GERMAN_INVENTORY)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn syllabify(input: &str) -> Vec<String> {
+        syls_from_word(
+            &Word::try_from(input).unwrap(),
+            &GermanSyllableRule::default(),
+        )
+        .iter()
+        .map(|x| x.to_string())
+        .collect()
+    }
+
+    #[test]
+    fn onsets() {
+        assert_eq!(syllabify("ˈʃtuːdi̯ʊm"), ["ˈʃtuː", "di\u{0311}ʊm"]);
+        assert_eq!(syllabify("ˈʃpʁɛçən"), ["ˈʃpʁɛ", "c\u{0327}ən"]);
+        assert_eq!(syllabify("ˈfɛnstɐ"), ["ˈfɛn", "stɐ"]);
+        assert_eq!(syllabify("ˈzɪŋən"), ["ˈzɪŋ", "ən"]);
+    }
+
+    #[test]
+    fn diphthongs() {
+        assert_eq!(syllabify("ˈhaʊs"), ["ˈhaʊs"]);
+        assert_eq!(syllabify("ˈaɪ̯nʃtaɪ̯n"), ["ˈaɪ\u{0311}n", "ʃtaɪ\u{0311}n"]);
+        assert_eq!(syllabify("ˈhɔʏtə"), ["ˈhɔʏ", "tə"]);
+        assert_eq!(syllabify("ˈʁuːə"), ["ˈʁuː", "ə"]);
+    }
+}
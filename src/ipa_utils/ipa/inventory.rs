@@ -0,0 +1,262 @@
+use crate::ipa_utils::ipa::english::EnglishDialect;
+use crate::ipa_utils::ipa::*;
+
+// the segments of a language, to check transcriptions and map foreign segments onto native ones
+
+// see https://en.wikipedia.org/wiki/English_phonology, general american and received pronunciation
+const ENGLISH_CONSONANT_LIST: [&str; 24] = [
+    "p", "b", "t", "d", "k", "ɡ", "t͡ʃ", "d͡ʒ", "f", "v", "θ", "ð", "s", "z", "ʃ", "ʒ", "h", "m",
+    "n", "ŋ", "l", "ɹ", "j", "w",
+];
+
+const ENGLISH_VOWEL_LIST: [&str; 14] = [
+    "i", "ɪ", "ɛ", "æ", "ɑ", "ɒ", "ɔ", "ʊ", "u", "ʌ", "ə", "ɜ", "ɚ", "ɝ",
+];
+
+// these say where a segment is in the syllable, not what it is
+const POSITIONAL_DIACRITICS: [Diacritic; 2] = [Diacritic::Syllabic, Diacritic::NonSyllabic];
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PhonemeInventory {
+    pub consonants: Vec<Letter>,
+    // monophthongs only, the parts of diphthongs don't have to be in here
+    pub vowels: Vec<Letter>,
+    pub diphthongs: Vec<Word>,
+}
+
+fn distinctive_diacritics(letter: &Letter) -> Vec<Diacritic> {
+    letter
+        .diacritics
+        .iter()
+        .flatten()
+        .filter(|x| !POSITIONAL_DIACRITICS.contains(x))
+        .copied()
+        .collect()
+}
+
+fn same_phoneme(a: &Letter, b: &Letter) -> bool {
+    a.ipa_type == b.ipa_type && distinctive_diacritics(a) == distinctive_diacritics(b)
+}
+
+fn is_vowel(letter: &Letter) -> bool {
+    matches!(letter.ipa_type, LetterType::Vowel(_))
+}
+
+impl PhonemeInventory {
+    pub fn new(
+        consonants: &[&str],
+        vowels: &[&str],
+        diphthongs: &[&str],
+    ) -> Result<Self, IpaParseError> {
+        let letters = |list: &[&str]| {
+            list.iter()
+                .map(|x| match Word::try_from(*x)?.0.as_slice() {
+                    [letter] => Ok(letter.clone()),
+                    letters => Err(IpaParseError::NotOneLetter {
+                        value: x.to_string(),
+                        letters: letters.len(),
+                    }),
+                })
+                .collect::<Result<Vec<Letter>, IpaParseError>>()
+        };
+        Ok(Self {
+            consonants: letters(consonants)?,
+            vowels: letters(vowels)?,
+            diphthongs: diphthongs
+                .iter()
+                .map(|x| Word::try_from(*x))
+                .collect::<Result<Vec<Word>, _>>()?,
+        })
+    }
+
+    pub fn english(dialect: EnglishDialect) -> Self {
+        Self::new(
+            &ENGLISH_CONSONANT_LIST,
+            &ENGLISH_VOWEL_LIST,
+            dialect.diphthongs(),
+        )
+        .expect("english inventory is valid ipa")
+    }
+
+    // syllabic and non-syllabic variants count as the same phoneme
+    pub fn contains(&self, letter: &Letter) -> bool {
+        let list = if is_vowel(letter) {
+            &self.vowels
+        } else {
+            &self.consonants
+        };
+        list.iter().any(|x| same_phoneme(x, letter))
+    }
+
+    // the length of the diphthong starting at letters[0], if there is one
+    pub fn diphthong_at(&self, letters: &[Letter]) -> Option<usize> {
+        self.diphthongs
+            .iter()
+            .filter(|x| {
+                x.0.len() <= letters.len()
+                    && x.0.iter().zip(letters).all(|(a, b)| same_phoneme(a, b))
+            })
+            .map(|x| x.0.len())
+            .max()
+    }
+
+    // whether the letters are one of the diphthongs or triphthongs, not just a part of one
+    pub fn is_diphthong(&self, letters: &[Letter]) -> bool {
+        self.diphthongs.iter().any(|x| {
+            x.0.len() == letters.len() && x.0.iter().zip(letters).all(|(a, b)| same_phoneme(a, b))
+        })
+    }
+
+    // indices of the letters that aren't in the inventory, suprasegmentals are skipped
+    pub fn unknown_segments(&self, word: &Word) -> Vec<usize> {
+        let mut out = vec![];
+        let mut i = 0;
+        while i < word.0.len() {
+            let letter = &word.0[i];
+            if let Some(len) = self.diphthong_at(&word.0[i..]) {
+                i += len;
+                continue;
+            }
+            if !matches!(letter.ipa_type, LetterType::Suprasegmental(_)) && !self.contains(letter) {
+                out.push(i);
+            }
+            i += 1;
+        }
+        out
+    }
+
+    pub fn nearest_vowel(&self, letter: &Letter) -> Option<&Letter> {
+        nearest(&self.vowels, letter)
+    }
+
+    pub fn nearest_consonant(&self, letter: &Letter) -> Option<&Letter> {
+        nearest(&self.consonants, letter)
+    }
+
+    // replaces every unknown segment with the nearest one of the inventory, like a loanword
+    pub fn adapt(&self, word: &Word) -> Word {
+        let unknown = self.unknown_segments(word);
+        let letters = word.0.iter().enumerate().map(|(i, letter)| {
+            if !unknown.contains(&i) {
+                return letter.clone();
+            }
+            let nearest = if is_vowel(letter) {
+                self.nearest_vowel(letter)
+            } else {
+                self.nearest_consonant(letter)
+            };
+            let Some(nearest) = nearest else {
+                return letter.clone();
+            };
+            let mut diacritics = distinctive_diacritics(nearest);
+            diacritics.extend(
+                POSITIONAL_DIACRITICS
+                    .iter()
+                    .filter(|x| letter.has_diacritic(**x)),
+            );
+            Letter {
+                ipa_type: nearest.ipa_type.clone(),
+                diacritics: (!diacritics.is_empty()).then_some(diacritics),
+            }
+        });
+        Word(letters.collect())
+    }
+}
+
+// clusters of letters like the onsets of a language, diacritics aren't compared
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ClusterList(pub Vec<Word>);
+
+impl ClusterList {
+    pub fn new(clusters: &[&str]) -> Result<Self, IpaParseError> {
+        Ok(Self(
+            clusters
+                .iter()
+                .map(|x| Word::try_from(*x))
+                .collect::<Result<Vec<Word>, _>>()?,
+        ))
+    }
+
+    pub fn contains(&self, letters: &[&Letter]) -> bool {
+        self.0.iter().any(|cluster| {
+            cluster.0.len() == letters.len()
+                && cluster
+                    .0
+                    .iter()
+                    .zip(letters)
+                    .all(|(a, b)| a.ipa_type == b.ipa_type)
+        })
+    }
+}
+
+fn nearest<'a>(list: &'a [Letter], letter: &Letter) -> Option<&'a Letter> {
+    list.iter()
+        .map(|x| (x, x.distance(letter)))
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(x, _)| x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn letter(input: &str) -> Letter {
+        Letter::try_from(input).unwrap()
+    }
+
+    #[test]
+    fn english_segments() {
+        let inventory = PhonemeInventory::english(EnglishDialect::GeneralAmerican);
+        assert!(inventory.contains(&letter("ʃ")));
+        assert!(inventory.contains(&letter("n\u{0329}")));
+        assert!(!inventory.contains(&letter("x")));
+        assert!(!inventory.contains(&letter("y")));
+        // e only appears in diphthongs
+        let word = Word::try_from("ˈbeɪbi").unwrap();
+        assert!(inventory.unknown_segments(&word).is_empty());
+        let word = Word::try_from("ˈbɛɾɚ").unwrap();
+        assert_eq!(inventory.unknown_segments(&word), [3]);
+    }
+
+    #[test]
+    fn nearest_segments() {
+        let inventory = PhonemeInventory::english(EnglishDialect::GeneralAmerican);
+        assert_eq!(inventory.nearest_vowel(&letter("y")), Some(&letter("i")));
+        assert_eq!(inventory.nearest_vowel(&letter("ɯ")), Some(&letter("u")));
+        assert_eq!(
+            inventory.nearest_consonant(&letter("ɸ")),
+            Some(&letter("f"))
+        );
+    }
+
+    #[test]
+    fn adapt_loanword() {
+        let inventory = PhonemeInventory::english(EnglishDialect::GeneralAmerican);
+        // german schön
+        let adapted = inventory.adapt(&Word::try_from("ʃøːn").unwrap());
+        assert!(inventory.unknown_segments(&adapted).is_empty());
+        assert_eq!(adapted.0.len(), 4);
+        assert_eq!(adapted.0[0], letter("ʃ"));
+    }
+
+    #[test]
+    fn one_letter_per_segment() {
+        assert_eq!(
+            PhonemeInventory::new(&[""], &["a"], &[]),
+            Err(IpaParseError::NotOneLetter {
+                value: String::new(),
+                letters: 0,
+            })
+        );
+        assert!(PhonemeInventory::new(&["p", "t͡s"], &["a"], &[]).is_ok());
+        assert_eq!(
+            PhonemeInventory::new(&["ts"], &["a"], &[]),
+            Err(IpaParseError::NotOneLetter {
+                value: "ts".to_string(),
+                letters: 2,
+            })
+        );
+    }
+}
@@ -0,0 +1,57 @@
+use serde::{de, Deserialize, Deserializer, Serializer};
+use std::fmt::Display;
+
+// compact form for serde, use with #[serde(with = "ipa_string")] on a Word or Letter field
+// the derived form keeps the whole structure instead
+
+pub fn serialize<T: Display, S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(value)
+}
+
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: for<'a> TryFrom<&'a str>,
+    for<'a> <T as TryFrom<&'a str>>::Error: Display,
+    D: Deserializer<'de>,
+{
+    let value = String::deserialize(deserializer)?;
+    T::try_from(value.as_str()).map_err(de::Error::custom)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ipa_utils::ipa::*;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Entry {
+        #[serde(with = "super")]
+        word: Word,
+        #[serde(with = "super")]
+        letter: Letter,
+    }
+
+    #[test]
+    fn compact_round_trip() {
+        let entry = Entry {
+            word: Word::try_from("ˈt͡ʃɜ˞t͡ʃ").unwrap(),
+            letter: Letter::try_from("pʰ").unwrap(),
+        };
+        let json = serde_json::to_string(&entry).unwrap();
+        assert_eq!(json, r#"{"word":"ˈt͡ʃɜ˞t͡ʃ","letter":"pʰ"}"#);
+        assert_eq!(serde_json::from_str::<Entry>(&json).unwrap(), entry);
+        assert!(serde_json::from_str::<Entry>(r#"{"word":"%","letter":"p"}"#).is_err());
+    }
+
+    #[test]
+    fn structured_round_trip() {
+        let word = Word::try_from("ˈkʼæ̃t͡ʃ˥˩ǃ").unwrap();
+        let json = serde_json::to_string(&word).unwrap();
+        assert_eq!(serde_json::from_str::<Word>(&json).unwrap(), word);
+
+        let syls = syls_from_word(&word, &english::EnglishSyllableRule::default());
+        let json = serde_json::to_string(&syls).unwrap();
+        let back: Vec<Syllable> = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, syls);
+    }
+}
@@ -1,11 +1,29 @@
 #![allow(dead_code)]
-use anyhow::anyhow;
+use anyhow::{anyhow, Context};
 use core::fmt;
+use english::{EnglishDialect, EnglishSyllableRule};
+use inventory::PhonemeInventory;
+use normalize::Normalizer;
 use phf::{phf_map, Map};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::LazyLock;
 use std::vec;
 use unicode_segmentation::UnicodeSegmentation;
 
+pub mod ascii;
+pub mod broad;
+pub mod distance;
 pub mod english;
+pub mod evaluation;
+pub mod features;
+pub mod french;
+pub mod german;
+pub mod inventory;
+#[cfg(feature = "serde")]
+pub mod ipa_string;
+pub mod normalize;
+pub mod spanish;
 
 const DIACRITIC_MAP: Map<char, Diacritic> = phf_map! {
     '\u{0329}'=>Diacritic::Syllabic,
@@ -31,7 +49,6 @@ const DIACRITIC_MAP: Map<char, Diacritic> = phf_map! {
     '\u{033B}'=>Diacritic::Laminal,
     '\u{031F}'=>Diacritic::Advanced,// has other
     '\u{0320}'=>Diacritic::Retracted,
-    '\u{0304}'=>Diacritic::Retracted,
     '\u{0308}'=>Diacritic::Centralized,
     '\u{033D}'=>Diacritic::MidCentralized,
     '\u{031D}'=>Diacritic::Raised,
@@ -53,6 +70,19 @@ const DIACRITIC_MAP: Map<char, Diacritic> = phf_map! {
     '\u{AB6B}'=>Diacritic::RetractedTongueRoot,
     '\u{0303}'=>Diacritic::Nasalized,
     '\u{02DE}'=>Diacritic::Rhoticity,
+    '\u{030B}'=>Diacritic::Pitch(PitchDiacritic::ExtraHigh),
+    '\u{0301}'=>Diacritic::Pitch(PitchDiacritic::High),
+    '\u{0304}'=>Diacritic::Pitch(PitchDiacritic::Mid),
+    '\u{0300}'=>Diacritic::Pitch(PitchDiacritic::Low),
+    '\u{030F}'=>Diacritic::Pitch(PitchDiacritic::ExtraLow),
+    '\u{030C}'=>Diacritic::Pitch(PitchDiacritic::Rising),
+    '\u{0302}'=>Diacritic::Pitch(PitchDiacritic::Falling),
+    '\u{1DC8}'=>Diacritic::Pitch(PitchDiacritic::Peaking),
+    '\u{1DC9}'=>Diacritic::Pitch(PitchDiacritic::Dipping),
+    '\u{1DC4}'=>Diacritic::Pitch(PitchDiacritic::MidRising),
+    '\u{1DC5}'=>Diacritic::Pitch(PitchDiacritic::LowRising),
+    '\u{1DC7}'=>Diacritic::Pitch(PitchDiacritic::HighFalling),
+    '\u{1DC6}'=>Diacritic::Pitch(PitchDiacritic::MidFalling),
 };
 
 const SUPRASEGREMENTAL_MAP: Map<char, Suprasegmental> = phf_map! {
@@ -67,10 +97,24 @@ const SUPRASEGREMENTAL_MAP: Map<char, Suprasegmental> = phf_map! {
     '\u{2016}' => Suprasegmental::MajorBreak,
     '\u{2197}' => Suprasegmental::GlobalRise,
     '\u{2198}' => Suprasegmental::GlobalFall,
+    '\u{A71B}' => Suprasegmental::Upstep,
+    '\u{A71C}' => Suprasegmental::Downstep,
+};
 
+const CHAO_TONE_LETTER_MAP: Map<char, (ChaoToneLetterHeight, bool)> = phf_map! {
+    '\u{02E5}' => (ChaoToneLetterHeight::ExtraHigh, false),
+    '\u{02E6}' => (ChaoToneLetterHeight::High, false),
+    '\u{02E7}' => (ChaoToneLetterHeight::Mid, false),
+    '\u{02E8}' => (ChaoToneLetterHeight::Low, false),
+    '\u{02E9}' => (ChaoToneLetterHeight::ExtraLow, false),
+    '\u{A712}' => (ChaoToneLetterHeight::ExtraHigh, true),
+    '\u{A713}' => (ChaoToneLetterHeight::High, true),
+    '\u{A714}' => (ChaoToneLetterHeight::Mid, true),
+    '\u{A715}' => (ChaoToneLetterHeight::Low, true),
+    '\u{A716}' => (ChaoToneLetterHeight::ExtraLow, true),
 };
 
-const PULMONIC_CONSONANT_LIST: [(PulmonicConsonant, &[char]); 111] = [
+const PULMONIC_CONSONANT_LIST: [(PulmonicConsonant, &[char]); 109] = [
     (
         PulmonicConsonant {
             manner: PulmonicConsonantManner::NonSibilantFricative,
@@ -109,7 +153,7 @@ const PULMONIC_CONSONANT_LIST: [(PulmonicConsonant, &[char]); 111] = [
             place: ConsonantPlace::Labiodental,
             voicing: ConsonantVoicing::Voiceless,
         },
-        &['\u{006D}'],
+        &['\u{0271}', '\u{030A}'],
     ),
     (
         PulmonicConsonant {
@@ -533,7 +577,7 @@ const PULMONIC_CONSONANT_LIST: [(PulmonicConsonant, &[char]); 111] = [
             place: ConsonantPlace::Palatal,
             voicing: ConsonantVoicing::Voiceless,
         },
-        &['\u{00E7}'],
+        &['\u{0063}', '\u{0327}'],
     ),
     (
         PulmonicConsonant {
@@ -842,23 +886,7 @@ const PULMONIC_CONSONANT_LIST: [(PulmonicConsonant, &[char]); 111] = [
     (
         PulmonicConsonant {
             manner: PulmonicConsonantManner::LateralFricative,
-            place: ConsonantPlace::Palatal,
-            voicing: ConsonantVoicing::Voiced,
-        },
-        &['\u{029F}', '\u{031D}'],
-    ),
-    (
-        PulmonicConsonant {
-            manner: PulmonicConsonantManner::LateralFricative,
-            place: ConsonantPlace::Palatal,
-            voicing: ConsonantVoicing::Voiceless,
-        },
-        &['\u{1DF04}'],
-    ),
-    (
-        PulmonicConsonant {
-            manner: PulmonicConsonantManner::LateralFricative,
-            place: ConsonantPlace::Palatal,
+            place: ConsonantPlace::Velar,
             voicing: ConsonantVoicing::Voiced,
         },
         &['\u{029F}', '\u{031D}'],
@@ -866,7 +894,7 @@ const PULMONIC_CONSONANT_LIST: [(PulmonicConsonant, &[char]); 111] = [
     (
         PulmonicConsonant {
             manner: PulmonicConsonantManner::LateralFricative,
-            place: ConsonantPlace::Palatal,
+            place: ConsonantPlace::Velar,
             voicing: ConsonantVoicing::Voiceless,
         },
         &['\u{1DF04}'],
@@ -961,6 +989,205 @@ const PULMONIC_CONSONANT_LIST: [(PulmonicConsonant, &[char]); 111] = [
     ),
 ];
 
+const NON_PULMONIC_CONSONANT_LIST: [(NonPulmonicConsonant, &[char]); 28] = [
+    (
+        NonPulmonicConsonant {
+            kind: NonPulmonicConsonantKind::Click,
+            place: ConsonantPlace::Bilabial,
+        },
+        &['\u{0298}'],
+    ),
+    (
+        NonPulmonicConsonant {
+            kind: NonPulmonicConsonantKind::Click,
+            place: ConsonantPlace::Dental,
+        },
+        &['\u{01C0}'],
+    ),
+    (
+        NonPulmonicConsonant {
+            kind: NonPulmonicConsonantKind::Click,
+            place: ConsonantPlace::Postalveolar,
+        },
+        &['\u{01C3}'],
+    ),
+    (
+        NonPulmonicConsonant {
+            kind: NonPulmonicConsonantKind::Click,
+            place: ConsonantPlace::Palatal,
+        },
+        &['\u{01C2}'],
+    ),
+    (
+        NonPulmonicConsonant {
+            kind: NonPulmonicConsonantKind::LateralClick,
+            place: ConsonantPlace::Aveolar,
+        },
+        &['\u{01C1}'],
+    ),
+    (
+        NonPulmonicConsonant {
+            kind: NonPulmonicConsonantKind::Implosive,
+            place: ConsonantPlace::Bilabial,
+        },
+        &['\u{0253}'],
+    ),
+    (
+        NonPulmonicConsonant {
+            kind: NonPulmonicConsonantKind::Implosive,
+            place: ConsonantPlace::Aveolar,
+        },
+        &['\u{0257}'],
+    ),
+    (
+        NonPulmonicConsonant {
+            kind: NonPulmonicConsonantKind::Implosive,
+            place: ConsonantPlace::Retroflex,
+        },
+        &['\u{1D91}'],
+    ),
+    (
+        NonPulmonicConsonant {
+            kind: NonPulmonicConsonantKind::Implosive,
+            place: ConsonantPlace::Palatal,
+        },
+        &['\u{0284}'],
+    ),
+    (
+        NonPulmonicConsonant {
+            kind: NonPulmonicConsonantKind::Implosive,
+            place: ConsonantPlace::Velar,
+        },
+        &['\u{0260}'],
+    ),
+    (
+        NonPulmonicConsonant {
+            kind: NonPulmonicConsonantKind::Implosive,
+            place: ConsonantPlace::Uvular,
+        },
+        &['\u{029B}'],
+    ),
+    (
+        NonPulmonicConsonant {
+            kind: NonPulmonicConsonantKind::Ejective(PulmonicConsonantManner::Plosive),
+            place: ConsonantPlace::Bilabial,
+        },
+        &['\u{0070}', '\u{02BC}'],
+    ),
+    (
+        NonPulmonicConsonant {
+            kind: NonPulmonicConsonantKind::Ejective(PulmonicConsonantManner::Plosive),
+            place: ConsonantPlace::Aveolar,
+        },
+        &['\u{0074}', '\u{02BC}'],
+    ),
+    (
+        NonPulmonicConsonant {
+            kind: NonPulmonicConsonantKind::Ejective(PulmonicConsonantManner::Plosive),
+            place: ConsonantPlace::Retroflex,
+        },
+        &['\u{0288}', '\u{02BC}'],
+    ),
+    (
+        NonPulmonicConsonant {
+            kind: NonPulmonicConsonantKind::Ejective(PulmonicConsonantManner::Plosive),
+            place: ConsonantPlace::Palatal,
+        },
+        &['\u{0063}', '\u{02BC}'],
+    ),
+    (
+        NonPulmonicConsonant {
+            kind: NonPulmonicConsonantKind::Ejective(PulmonicConsonantManner::Plosive),
+            place: ConsonantPlace::Velar,
+        },
+        &['\u{006B}', '\u{02BC}'],
+    ),
+    (
+        NonPulmonicConsonant {
+            kind: NonPulmonicConsonantKind::Ejective(PulmonicConsonantManner::Plosive),
+            place: ConsonantPlace::Uvular,
+        },
+        &['\u{0071}', '\u{02BC}'],
+    ),
+    (
+        NonPulmonicConsonant {
+            kind: NonPulmonicConsonantKind::Ejective(PulmonicConsonantManner::Plosive),
+            place: ConsonantPlace::Pharyngeal,
+        },
+        &['\u{02A1}', '\u{02BC}'],
+    ),
+    (
+        NonPulmonicConsonant {
+            kind: NonPulmonicConsonantKind::Ejective(PulmonicConsonantManner::NonSibilantFricative),
+            place: ConsonantPlace::Bilabial,
+        },
+        &['\u{0278}', '\u{02BC}'],
+    ),
+    (
+        NonPulmonicConsonant {
+            kind: NonPulmonicConsonantKind::Ejective(PulmonicConsonantManner::NonSibilantFricative),
+            place: ConsonantPlace::Labiodental,
+        },
+        &['\u{0066}', '\u{02BC}'],
+    ),
+    (
+        NonPulmonicConsonant {
+            kind: NonPulmonicConsonantKind::Ejective(PulmonicConsonantManner::NonSibilantFricative),
+            place: ConsonantPlace::Dental,
+        },
+        &['\u{03B8}', '\u{02BC}'],
+    ),
+    (
+        NonPulmonicConsonant {
+            kind: NonPulmonicConsonantKind::Ejective(PulmonicConsonantManner::NonSibilantFricative),
+            place: ConsonantPlace::Velar,
+        },
+        &['\u{0078}', '\u{02BC}'],
+    ),
+    (
+        NonPulmonicConsonant {
+            kind: NonPulmonicConsonantKind::Ejective(PulmonicConsonantManner::NonSibilantFricative),
+            place: ConsonantPlace::Uvular,
+        },
+        &['\u{03C7}', '\u{02BC}'],
+    ),
+    (
+        NonPulmonicConsonant {
+            kind: NonPulmonicConsonantKind::Ejective(PulmonicConsonantManner::SibilantFricative),
+            place: ConsonantPlace::Aveolar,
+        },
+        &['\u{0073}', '\u{02BC}'],
+    ),
+    (
+        NonPulmonicConsonant {
+            kind: NonPulmonicConsonantKind::Ejective(PulmonicConsonantManner::SibilantFricative),
+            place: ConsonantPlace::Postalveolar,
+        },
+        &['\u{0283}', '\u{02BC}'],
+    ),
+    (
+        NonPulmonicConsonant {
+            kind: NonPulmonicConsonantKind::Ejective(PulmonicConsonantManner::SibilantFricative),
+            place: ConsonantPlace::Retroflex,
+        },
+        &['\u{0282}', '\u{02BC}'],
+    ),
+    (
+        NonPulmonicConsonant {
+            kind: NonPulmonicConsonantKind::Ejective(PulmonicConsonantManner::SibilantFricative),
+            place: ConsonantPlace::Palatal,
+        },
+        &['\u{0255}', '\u{02BC}'],
+    ),
+    (
+        NonPulmonicConsonant {
+            kind: NonPulmonicConsonantKind::Ejective(PulmonicConsonantManner::LateralFricative),
+            place: ConsonantPlace::Aveolar,
+        },
+        &['\u{026C}', '\u{02BC}'],
+    ),
+];
+
 const VOWEL_LIST: [(Vowel, &[char]); 33] = [
     (
         Vowel {
@@ -1088,7 +1315,7 @@ const VOWEL_LIST: [(Vowel, &[char]); 33] = [
             backness: VowelBackness::Back,
             roundedness: VowelRoundedness::Unrounded,
         },
-        &['\u{006F}'],
+        &['\u{0264}'],
     ),
     (
         Vowel {
@@ -1230,16 +1457,159 @@ const VOWEL_LIST: [(Vowel, &[char]); 33] = [
 ];
 
 const MISC_LETTER_LIST: [(MiscLetter, &[char]); 6] = [
-    (MiscLetter::VoicedLabialPalatalApproximant, &['w']),
-    (MiscLetter::VoicelessLabialPalatalApproximant, &['\u{028D}']),
-    (MiscLetter::VoicelessLabialVelarApproximant, &['\u{0265}']),
-    (MiscLetter::VoicedLabialVelarApproximant, &['\u{0265}']),
+    (MiscLetter::VoicedLabialVelarApproximant, &['w']),
+    (MiscLetter::VoicelessLabialVelarApproximant, &['\u{028D}']),
+    (MiscLetter::VoicedLabialPalatalApproximant, &['\u{0265}']),
+    (
+        MiscLetter::VoicelessLabialPalatalApproximant,
+        &['\u{0265}', '\u{030A}'],
+    ),
     (MiscLetter::SjSound, &['ɧ']),
     (MiscLetter::VelarizedAveolar, &['\u{006C}', '\u{02E0}']),
 ];
 
-const REPLACE_LIST: [(char, &str); 5] = [
+// diacritics and anything else that combines with the letter in front of it
+fn is_mark(c: char) -> bool {
+    DIACRITIC_MAP.contains_key(&c)
+        || matches!(
+            c,
+            '\u{0300}'..='\u{036F}'
+                | '\u{1AB0}'..='\u{1AFF}'
+                | '\u{1DC0}'..='\u{1DFF}'
+                | '\u{20D0}'..='\u{20FF}'
+        )
+}
+
+// a grapheme split into its base chars and the marks on them, e.g. "ŋ̊ʷ" is ŋ with ̊ and ʷ
+#[derive(Debug, Clone, PartialEq)]
+struct Grapheme<'a> {
+    base: Cow<'a, str>,
+    marks: Vec<char>,
+}
+
+impl<'a> Grapheme<'a> {
+    // most graphemes have no marks, those don't need to be copied
+    fn split(value: &'a str) -> Self {
+        if !value.chars().any(is_mark) {
+            return Self {
+                base: Cow::Borrowed(value),
+                marks: vec![],
+            };
+        }
+        let (marks, base): (Vec<char>, Vec<char>) = value.chars().partition(|c| is_mark(*c));
+        Self {
+            base: Cow::Owned(base.into_iter().collect()),
+            marks,
+        }
+    }
+
+    // the marks left over once the entry took its own, None if the entry needs marks we don't have
+    // or something is left that isn't a diacritic
+    fn remaining_marks(&self, entry: &[char]) -> Option<Vec<char>> {
+        let mut marks = self.marks.clone();
+        for c in entry.iter().filter(|c| is_mark(**c)) {
+            let i = marks.iter().position(|x| x == c)?;
+            marks.remove(i);
+        }
+        marks
+            .iter()
+            .all(|c| DIACRITIC_MAP.contains_key(c))
+            .then_some(marks)
+    }
+}
+
+// maps the base chars of every entry in a list to the entries, so a grapheme only has to be
+// compared with the entries written with the same letter
+type BaseIndex = HashMap<String, Vec<usize>>;
+
+fn base_index<T>(list: &[(T, &[char])]) -> BaseIndex {
+    let mut index = BaseIndex::new();
+    for (i, (_, chars)) in list.iter().enumerate() {
+        let base = chars.iter().filter(|c| !is_mark(**c)).collect();
+        index.entry(base).or_default().push(i);
+    }
+    index
+}
+
+// the entries fitting a grapheme that take the most of its marks, as ɾ̥ is more specific than ɾ.
+// more than one entry means the grapheme is ambiguous
+struct BestEntries<T> {
+    used: usize,
+    entries: Vec<(T, &'static [char])>,
+}
+
+impl<T> Default for BestEntries<T> {
+    fn default() -> Self {
+        Self {
+            used: 0,
+            entries: vec![],
+        }
+    }
+}
+
+impl<T> BestEntries<T> {
+    // adds the fitting entries of list, can be called with several lists
+    fn add<U>(
+        &mut self,
+        list: &'static [(U, &'static [char])],
+        index: &BaseIndex,
+        grapheme: &Grapheme,
+        convert: fn(&U) -> T,
+    ) {
+        for i in index.get(grapheme.base.as_ref()).into_iter().flatten() {
+            let (entry, chars) = &list[*i];
+            let Some(remaining) = grapheme.remaining_marks(chars) else {
+                continue;
+            };
+            let used = grapheme.marks.len() - remaining.len();
+            if used > self.used || self.entries.is_empty() {
+                self.used = used;
+                self.entries.clear();
+            }
+            if used == self.used {
+                self.entries.push((convert(entry), *chars));
+            }
+        }
+    }
+}
+
+// offsets in the errors are 0 as the value is a single grapheme
+fn unique_entry<T: Clone>(
+    list: &'static [(T, &'static [char])],
+    index: &BaseIndex,
+    value: &str,
+    category: LetterCategory,
+    convert: fn(&T) -> LetterType,
+) -> Result<T, IpaParseError> {
+    let mut best = BestEntries::default();
+    best.add(list, index, &Grapheme::split(value), T::clone);
+    match best.entries.len() {
+        0 => Err(IpaParseError::UnknownGrapheme {
+            grapheme: value.to_string(),
+            offset: 0,
+            tried: vec![category],
+        }),
+        1 => Ok(best.entries.remove(0).0),
+        _ => Err(IpaParseError::AmbiguousGrapheme {
+            grapheme: value.to_string(),
+            offset: 0,
+            candidates: best.entries.iter().map(|(x, _)| convert(x)).collect(),
+        }),
+    }
+}
+
+static VOWEL_INDEX: LazyLock<BaseIndex> = LazyLock::new(|| base_index(&VOWEL_LIST));
+static PULMONIC_CONSONANT_INDEX: LazyLock<BaseIndex> =
+    LazyLock::new(|| base_index(&PULMONIC_CONSONANT_LIST));
+static NON_PULMONIC_CONSONANT_INDEX: LazyLock<BaseIndex> =
+    LazyLock::new(|| base_index(&NON_PULMONIC_CONSONANT_LIST));
+static MISC_LETTER_INDEX: LazyLock<BaseIndex> = LazyLock::new(|| base_index(&MISC_LETTER_LIST));
+
+const TIE_BARS: [char; 2] = ['\u{0361}', '\u{035C}'];
+
+const REPLACE_LIST: [(char, &str); 6] = [
     ('ɫ', "l\u{02E0}"),
+    ('ç', "c\u{0327}"),
     ('ɚ', "\u{0259}\u{02DE}"),
     ('ɝ', "\u{025C}\u{02DE}"),
     ('ẽ', "e\u{0303}"),
@@ -1247,15 +1617,149 @@ const REPLACE_LIST: [(char, &str); 5] = [
 ];
 
 pub trait SyllableRule {
-    fn makes_valid_onset(&self, new: &Letter, rest: &Vec<Letter>) -> bool;
-    fn is_diphthong(&self, first: &Letter, second: &Letter) -> bool;
+    // rest is the onset found so far, starting next to the nucleus
+    fn makes_valid_onset(&self, new: &Letter, rest: &[Letter]) -> bool;
+    // nucleus is in order, the letter that would join it is the last one
+    fn is_diphthong(&self, nucleus: &[Letter]) -> bool;
+    // rest is the coda found so far, starting at the end of the syllable
+    fn makes_valid_coda(&self, _new: &Letter, _rest: &[Letter]) -> bool {
+        true
+    }
+    // whether the letter at index can be the nucleus of a syllable
+    fn is_syllabic(&self, word: &[Letter], index: usize) -> bool {
+        match word[index].ipa_type {
+            LetterType::Vowel(_) => word[index].is_syllabic(),
+            _ => self.is_syllabic_consonant(word, index),
+        }
+    }
+    // consonants only make a syllable when marked, unless the language says otherwise
+    fn is_syllabic_consonant(&self, word: &[Letter], index: usize) -> bool {
+        word[index].is_syllabic()
+    }
+    // whether the first letter of the onset of after also closes before, like the t in city
+    fn is_ambisyllabic(&self, _before: &Syllable, _after: &Syllable) -> bool {
+        false
+    }
+    // how many letters of the onset of after move to the coda of before once the stress is
+    // known, like a stressed short vowel taking the next consonant
+    fn resyllabify(&self, _before: &Syllable, _after: &Syllable) -> usize {
+        0
+    }
+    // the segments of the language, if the rule knows them
+    fn inventory(&self) -> Option<&PhonemeInventory> {
+        None
+    }
 }
 
-#[derive(Debug, Clone)]
+// the onset makes_valid_onset or coda makes_valid_coda would create, in order and without
+// suprasegmentals
+pub fn onset_with<'a>(new: &'a Letter, rest: &'a [Letter]) -> Vec<&'a Letter> {
+    std::iter::once(new)
+        .chain(rest.iter().rev())
+        .filter(|x| x.sonority().is_some())
+        .collect()
+}
+
+// by ISO 639-1 code, optionally with a region like en-GB
+pub fn syllable_rule_for_language(code: &str) -> Option<Box<dyn SyllableRule>> {
+    let code = code.to_lowercase().replace('_', "-");
+    let (language, region) = code.split_once('-').unwrap_or((code.as_str(), ""));
+    match language {
+        "en" => Some(Box::new(EnglishSyllableRule {
+            dialect: match region {
+                "gb" | "uk" => EnglishDialect::ReceivedPronunciation,
+                _ => EnglishDialect::GeneralAmerican,
+            },
+            ..Default::default()
+        })),
+        "de" => Some(Box::new(german::GermanSyllableRule::default())),
+        "es" => Some(Box::new(spanish::SpanishSyllableRule::default())),
+        "fr" => Some(Box::new(french::FrenchSyllableRule::default())),
+        _ => None,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SyllableStress {
+    Primary,
+    Secondary,
+    #[default]
+    Unstressed,
+}
+
+impl fmt::Display for SyllableStress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SyllableStress::Primary => write!(f, "{}", Suprasegmental::PrimaryStress),
+            SyllableStress::Secondary => write!(f, "{}", Suprasegmental::SecondaryStress),
+            SyllableStress::Unstressed => Ok(()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum VowelLength {
+    #[default]
+    Short,
+    HalfLong,
+    Long,
+}
+
+impl fmt::Display for VowelLength {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VowelLength::Short => Ok(()),
+            VowelLength::HalfLong => write!(f, "{}", Suprasegmental::HalfLong),
+            VowelLength::Long => write!(f, "{}", Suprasegmental::Long),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Syllable {
     pub onset: Vec<Letter>,
     pub nucleus: Vec<Letter>,
     pub coda: Vec<Letter>,
+    pub stress: SyllableStress,
+    pub length: VowelLength,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SyllableWeight {
+    Light,
+    Heavy,
+    Superheavy,
+}
+
+impl Syllable {
+    fn is_empty(&self) -> bool {
+        self.onset.is_empty() && self.nucleus.is_empty() && self.coda.is_empty()
+    }
+    // a short nucleus has one mora, long vowels and diphthongs two, every coda consonant adds one
+    pub fn morae(&self) -> usize {
+        let vowels = self
+            .nucleus
+            .iter()
+            .filter(|x| x.sonority().is_some())
+            .count();
+        let nucleus = if self.length == VowelLength::Long || vowels > 1 {
+            2
+        } else {
+            1
+        };
+        nucleus + self.coda.iter().filter(|x| x.sonority().is_some()).count()
+    }
+    pub fn weight(&self) -> SyllableWeight {
+        match self.morae() {
+            0 | 1 => SyllableWeight::Light,
+            2 => SyllableWeight::Heavy,
+            _ => SyllableWeight::Superheavy,
+        }
+    }
 }
 
 impl fmt::Display for Syllable {
@@ -1263,7 +1767,11 @@ impl fmt::Display for Syllable {
         let onset_str: String = self.onset.iter().map(|x| x.to_string()).collect();
         let nucleus_str: String = self.nucleus.iter().map(|x| x.to_string()).collect();
         let coda_str: String = self.coda.iter().map(|x| x.to_string()).collect();
-        write!(f, "{onset_str}{nucleus_str}{coda_str}")
+        write!(
+            f,
+            "{}{onset_str}{nucleus_str}{}{coda_str}",
+            self.stress, self.length
+        )
     }
 }
 
@@ -1273,65 +1781,88 @@ impl From<Word> for Vec<Letter> {
     }
 }
 
+// syllables are built back to front, so everything is reversed when finishing one
+fn push_syllable(out: &mut Vec<Syllable>, syl: &mut Syllable) {
+    let mut syl = std::mem::take(syl);
+    if syl.is_empty() {
+        return;
+    }
+    syl.onset.reverse();
+    syl.nucleus.reverse();
+    syl.coda.reverse();
+    out.push(syl);
+}
+
 pub fn syls_from_word(input: &Word, options: &dyn SyllableRule) -> Vec<Syllable> {
-    let mut out = vec![];
-    let mut onset = vec![];
-    let mut nucleus = vec![];
-    let mut coda = vec![];
-    let mut last = input.0.last().unwrap();
-    let mut found_nuc = match last {
-        Letter {
-            ipa_type: LetterType::Vowel(_),
-            diacritics: _,
-        } => {
-            nucleus.push(last.clone());
-            true
+    // length marks belong to the vowel in front of them, not to the coda
+    let mut letters: Vec<(&Letter, VowelLength, bool)> = vec![];
+    for (i, letter) in input.0.iter().enumerate() {
+        let length = match letter.ipa_type {
+            LetterType::Suprasegmental(Suprasegmental::Long) => Some(VowelLength::Long),
+            LetterType::Suprasegmental(Suprasegmental::HalfLong) => Some(VowelLength::HalfLong),
+            _ => None,
+        };
+        if let (
+            Some(length),
+            Some((
+                Letter {
+                    ipa_type: LetterType::Vowel(_),
+                    diacritics: _,
+                },
+                last_length,
+                _,
+            )),
+        ) = (length, letters.last_mut())
+        {
+            *last_length = length;
+            continue;
         }
-        _ => {
-            coda.push(last.clone());
-            false
+        letters.push((letter, VowelLength::Short, options.is_syllabic(&input.0, i)));
+    }
+
+    // whether a letter joins the nucleus in front of it, diphthongs are matched as a whole from
+    // the left, so aɪ and ɪɚ don't chain into aɪɚ
+    let mut joins = vec![false; letters.len()];
+    let mut nucleus: Vec<Letter> = vec![];
+    for (i, &(letter, _, syllabic)) in letters.iter().enumerate() {
+        // a long vowel can't start a diphthong
+        if !nucleus.is_empty() && letters[i - 1].1 == VowelLength::Short {
+            nucleus.push(letter.clone());
+            joins[i] = options.is_diphthong(&nucleus);
+            nucleus.pop();
+        }
+        if !syllabic {
+            nucleus.clear();
+        } else if !joins[i] {
+            nucleus = vec![letter.clone()];
+        } else {
+            nucleus.push(letter.clone());
         }
-    };
-    for letter in input.0.iter().rev().skip(1) {
-        //println!("{}, {}", &letter, &last);
+    }
+
+    let mut out = vec![];
+    let mut syl = Syllable::default();
+    let mut last: Option<(&Letter, bool)> = None;
+    for (i, &(letter, length, syllabic)) in letters.iter().enumerate().rev() {
+        //println!("{}, {:?}", &letter, &last);
         match (letter, last) {
-            // last letter was syllable break, split word
+            // stress marks belong to the syllable after them
             (
-                _,
                 Letter {
                     ipa_type:
                         LetterType::Suprasegmental(
-                            Suprasegmental::PrimaryStress
-                            | Suprasegmental::SecondaryStress
-                            | Suprasegmental::SyllableBreak,
+                            stress @ (Suprasegmental::PrimaryStress
+                            | Suprasegmental::SecondaryStress),
                         ),
                     diacritics: _,
                 },
+                _,
             ) => {
-                onset.reverse();
-                nucleus.reverse();
-                coda.reverse();
-                out.push(Syllable {
-                    onset,
-                    nucleus,
-                    coda,
-                });
-                onset = vec![];
-                nucleus = vec![];
-                coda = vec![];
-                match letter {
-                    Letter {
-                        ipa_type: LetterType::Vowel(_),
-                        diacritics: _,
-                    } => {
-                        nucleus.push(letter.clone());
-                        found_nuc = true
-                    }
-                    _ => {
-                        coda.push(letter.clone());
-                        found_nuc = false
-                    }
-                }
+                syl.stress = if *stress == Suprasegmental::PrimaryStress {
+                    SyllableStress::Primary
+                } else {
+                    SyllableStress::Secondary
+                };
             }
             // don't add syllable break to syllable
             (
@@ -1341,153 +1872,414 @@ pub fn syls_from_word(input: &Word, options: &dyn SyllableRule) -> Vec<Syllable>
                 },
                 _,
             ) => {}
-
-            // two consecutive vowels -> split if not diphtong
+            // last letter was syllable break or stress mark, split word
             (
-                Letter {
-                    ipa_type: LetterType::Vowel(_),
-                    diacritics: _,
-                },
-                Letter {
-                    ipa_type: LetterType::Vowel(_),
-                    diacritics: _,
-                },
+                _,
+                Some((
+                    Letter {
+                        ipa_type:
+                            LetterType::Suprasegmental(
+                                Suprasegmental::PrimaryStress
+                                | Suprasegmental::SecondaryStress
+                                | Suprasegmental::SyllableBreak,
+                            ),
+                        diacritics: _,
+                    },
+                    _,
+                )),
             ) => {
-                if !options.is_diphthong(letter, last) {
-                    onset.reverse();
-                    nucleus.reverse();
-                    coda.reverse();
-                    out.push(Syllable {
-                        onset,
-                        nucleus,
-                        coda,
-                    });
-                    onset = vec![];
-                    nucleus = vec![letter.clone()];
-                    coda = vec![];
-                    found_nuc = true
+                push_syllable(&mut out, &mut syl);
+                if syllabic {
+                    syl.nucleus.push(letter.clone());
+                    syl.length = length;
                 } else {
-                    nucleus.push(letter.clone());
+                    syl.coda.push(letter.clone());
                 }
             }
-            // two consecutive non vowels -> split if not allowed next to each other
-            (
-                Letter {
-                    ipa_type: LetterType::PulmonicConsonant(_) | LetterType::Suprasegmental(_),
-                    diacritics: _,
-                },
-                Letter {
-                    ipa_type: LetterType::PulmonicConsonant(_) | LetterType::Suprasegmental(_),
-                    diacritics: _,
-                },
-            ) => {
-                if !options.makes_valid_onset(letter, &onset) {
-                    onset.reverse();
-                    nucleus.reverse();
-                    coda.reverse();
-                    out.push(Syllable {
-                        onset,
-                        nucleus,
-                        coda,
-                    });
-                    onset = vec![];
-                    nucleus = vec![];
-                    coda = vec![letter.clone()];
-                    found_nuc = false;
-                } else if found_nuc {
-                    onset.push(letter.clone());
+
+            // two consecutive nuclei -> split if not diphtong
+            (_, Some((_, true))) if syllabic => {
+                if joins[i + 1] {
+                    syl.nucleus.push(letter.clone());
                 } else {
-                    coda.push(letter.clone());
+                    push_syllable(&mut out, &mut syl);
+                    syl.nucleus.push(letter.clone());
+                    syl.length = length;
                 }
             }
-            // vowel and non vowel -> split if nucleus already found
-            (
-                Letter {
-                    ipa_type: LetterType::Vowel(_),
-                    diacritics: _,
-                },
-                _,
-            ) => {
-                if found_nuc {
-                    onset.reverse();
-                    nucleus.reverse();
-                    coda.reverse();
-                    out.push(Syllable {
-                        onset,
-                        nucleus,
-                        coda,
-                    });
-                    onset = vec![];
-                    nucleus = vec![letter.clone()];
-                    coda = vec![];
-                    found_nuc = true;
-                } else {
-                    nucleus.push(letter.clone());
-                    found_nuc = true;
+            // nucleus and non nucleus -> split if nucleus already found
+            _ if syllabic => {
+                if !syl.nucleus.is_empty() {
+                    push_syllable(&mut out, &mut syl);
+                }
+                // off-glides and rhotic diphthongs like ɪɹ take the letter after them out of the coda
+                if syl.coda.last().is_some_and(|next| {
+                    matches!(next.ipa_type, LetterType::Vowel(_)) || joins[i + 1]
+                }) {
+                    syl.nucleus.extend(syl.coda.pop());
                 }
+                syl.nucleus.push(letter.clone());
+                syl.length = syl.length.max(length);
             }
-            // otherwise just append to current list
+            // otherwise add to onset, split if it wouldn't be a valid onset anymore
+            // the letter stays in the onset if it can't close the syllable before either
             _ => {
-                if found_nuc {
-                    onset.push(letter.clone());
+                if !syl.nucleus.is_empty()
+                    && !options.makes_valid_onset(letter, &syl.onset)
+                    && options.makes_valid_coda(letter, &[])
+                {
+                    push_syllable(&mut out, &mut syl);
+                    syl.coda.push(letter.clone());
+                } else if !syl.nucleus.is_empty() {
+                    syl.onset.push(letter.clone());
                 } else {
-                    coda.push(letter.clone());
+                    // a coda that can't follow the letter goes to the onset of the next syllable,
+                    // unless that onset isn't valid either, then the default split is kept
+                    if !options.makes_valid_coda(letter, &syl.coda) {
+                        if let Some(next) = out.last_mut() {
+                            let mut onset: Vec<Letter> = next.onset.iter().rev().cloned().collect();
+                            let valid = syl.coda.iter().all(|x| {
+                                let valid = options.makes_valid_onset(x, &onset);
+                                onset.push(x.clone());
+                                valid
+                            });
+                            if valid {
+                                syl.coda.reverse();
+                                syl.coda.append(&mut next.onset);
+                                next.onset = std::mem::take(&mut syl.coda);
+                            }
+                        }
+                    }
+                    syl.coda.push(letter.clone());
                 }
             }
         }
-        last = letter;
-    }
-    if found_nuc {
-        onset.reverse();
-        nucleus.reverse();
-        coda.reverse();
-        out.push(Syllable {
-            onset,
-            nucleus,
-            coda,
-        });
+        last = Some((letter, syllabic));
+    }
+    if !syl.nucleus.is_empty() {
+        push_syllable(&mut out, &mut syl);
+    }
+    // letters at the start that aren't a valid onset still belong to the first syllable
+    if let Some(first) = out.last_mut() {
+        if !syl.coda.is_empty() {
+            syl.coda.reverse();
+            syl.coda.append(&mut first.onset);
+            first.onset = syl.coda;
+        }
+        if syl.stress != SyllableStress::Unstressed {
+            first.stress = syl.stress;
+        }
     }
 
     out.reverse();
+    for i in 1..out.len() {
+        let (before, after) = out.split_at_mut(i);
+        let (before, after) = (&mut before[i - 1], &mut after[0]);
+        let moved = options.resyllabify(before, after).min(after.onset.len());
+        before.coda.extend(after.onset.drain(..moved));
+        let first = after.onset.first().cloned();
+        if let Some(first) = first.filter(|_| options.is_ambisyllabic(before, after)) {
+            before.coda.push(first);
+        }
+    }
     out
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Word(Vec<Letter>);
 
-impl TryFrom<&str> for Word {
-    // assumes diacritics are always behind their corresponding letters
-    type Error = anyhow::Error;
-    fn try_from(value: &str) -> Result<Self, Self::Error> {
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ParseOptions {
+    // join untied plosive + sibilant sequences like tʃ into affricates when they start a
+    // syllable, so the ts in cats stays a cluster. tied ones (t͡ʃ) are always joined
+    pub untied_affricates: bool,
+    // NFD and lookalike folding before parsing, error offsets are still in the input but the
+    // graphemes are normalized
+    pub normalizer: Option<Normalizer>,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self {
+            untied_affricates: false,
+            normalizer: Some(Normalizer::default()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LetterCategory {
+    Vowel,
+    Suprasegmental,
+    NonPulmonicConsonant,
+    PulmonicConsonant,
+    MiscLetter,
+}
+
+// the tables a grapheme is looked up in
+const LETTER_CATEGORY_LIST: [LetterCategory; 5] = [
+    LetterCategory::Vowel,
+    LetterCategory::Suprasegmental,
+    LetterCategory::NonPulmonicConsonant,
+    LetterCategory::PulmonicConsonant,
+    LetterCategory::MiscLetter,
+];
+
+impl LetterCategory {
+    // whether the table has the base letter of the grapheme, no matter the marks on it
+    fn knows_base(&self, grapheme: &Grapheme) -> bool {
+        let base = grapheme.base.as_ref();
+        match self {
+            LetterCategory::Vowel => VOWEL_INDEX.contains_key(base),
+            LetterCategory::Suprasegmental => base.chars().next().is_some_and(|x| {
+                SUPRASEGREMENTAL_MAP.contains_key(&x) || CHAO_TONE_LETTER_MAP.contains_key(&x)
+            }),
+            LetterCategory::NonPulmonicConsonant => NON_PULMONIC_CONSONANT_INDEX.contains_key(base),
+            LetterCategory::PulmonicConsonant => PULMONIC_CONSONANT_INDEX.contains_key(base),
+            LetterCategory::MiscLetter => MISC_LETTER_INDEX.contains_key(base),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum IpaParseError {
+    // offset is in bytes, grapheme includes the diacritics that couldn't be attached, tried holds
+    // the tables that have its base letter and is empty if none of them know it
+    UnknownGrapheme {
+        grapheme: String,
+        offset: usize,
+        tried: Vec<LetterCategory>,
+    },
+    DanglingTieBar {
+        offset: usize,
+    },
+    // more than one letter fits the grapheme equally well
+    AmbiguousGrapheme {
+        grapheme: String,
+        offset: usize,
+        candidates: Vec<LetterType>,
+    },
+    // a single letter was expected, like in a phoneme inventory
+    NotOneLetter {
+        value: String,
+        letters: usize,
+    },
+}
+
+impl fmt::Display for IpaParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IpaParseError::UnknownGrapheme {
+                grapheme,
+                offset,
+                tried,
+            } => write!(
+                f,
+                "unknown grapheme \"{grapheme}\" at byte {offset}, tried {tried:?}"
+            ),
+            IpaParseError::DanglingTieBar { offset } => {
+                write!(f, "tie bar without second letter at byte {offset}")
+            }
+            IpaParseError::AmbiguousGrapheme {
+                grapheme,
+                offset,
+                candidates,
+            } => write!(
+                f,
+                "ambiguous grapheme \"{grapheme}\" at byte {offset}, could be any of {candidates:?}"
+            ),
+            IpaParseError::NotOneLetter { value, letters } => {
+                write!(f, "expected one letter, \"{value}\" has {letters}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for IpaParseError {}
+
+// the error for a grapheme from_grapheme couldn't parse, with the readings it had
+fn grapheme_error(grapheme: &str, offset: usize, candidates: Vec<LetterType>) -> IpaParseError {
+    if !candidates.is_empty() {
+        return IpaParseError::AmbiguousGrapheme {
+            grapheme: grapheme.to_string(),
+            offset,
+            candidates,
+        };
+    }
+    let split = Grapheme::split(grapheme);
+    IpaParseError::UnknownGrapheme {
+        grapheme: grapheme.to_string(),
+        offset,
+        tried: LETTER_CATEGORY_LIST
+            .into_iter()
+            .filter(|x| x.knows_base(&split))
+            .collect(),
+    }
+}
+
+impl IpaParseError {
+    fn map_offset(self, f: impl Fn(usize) -> usize) -> Self {
+        match self {
+            IpaParseError::UnknownGrapheme {
+                grapheme,
+                offset,
+                tried,
+            } => IpaParseError::UnknownGrapheme {
+                grapheme,
+                offset: f(offset),
+                tried,
+            },
+            IpaParseError::DanglingTieBar { offset } => {
+                IpaParseError::DanglingTieBar { offset: f(offset) }
+            }
+            IpaParseError::AmbiguousGrapheme {
+                grapheme,
+                offset,
+                candidates,
+            } => IpaParseError::AmbiguousGrapheme {
+                grapheme,
+                offset: f(offset),
+                candidates,
+            },
+            not_one @ IpaParseError::NotOneLetter { .. } => not_one,
+        }
+    }
+}
+
+impl Word {
+    pub fn parse(value: &str, options: &ParseOptions) -> Result<Self, IpaParseError> {
+        let normalized = match &options.normalizer {
+            Some(normalizer) => normalizer.normalize_str(value),
+            None => Cow::Borrowed(value),
+        };
+        let parsed = Self::parse_normalized(&normalized, options);
+        match (normalized, &options.normalizer) {
+            // the substitutions are only needed to report the error
+            (Cow::Owned(_), Some(normalizer)) => parsed.map_err(|e| {
+                let (_, substitutions) = normalizer.normalize(value);
+                e.map_offset(|x| normalize::input_offset(value, &substitutions, x))
+            }),
+            _ => parsed,
+        }
+    }
+
+    fn parse_normalized(value: &str, options: &ParseOptions) -> Result<Self, IpaParseError> {
+        // assumes diacritics are always behind their corresponding letters
         let mut out = vec![];
 
-        let mut failed: Vec<&str> = vec![];
-        let mut last_err = anyhow!("couldn't convert to Letter, this Error should be unreachable");
-        for grapheme in UnicodeSegmentation::graphemes(value, true).rev() {
-            if ["/", "[", "]", "(", ")"].iter().any(|&z| grapheme == z) {
+        // graphemes that couldn't be parsed on their own are retried with the one in front
+        let mut failed: Option<(usize, usize)> = None;
+        for (offset, grapheme) in UnicodeSegmentation::grapheme_indices(value, true).rev() {
+            if ["/", "[", "]", "(", ")"].contains(&grapheme) {
                 continue;
             }
-            let mut combined = grapheme.to_string();
-            failed.iter().for_each(|z| combined.push_str(z));
+            let end = failed.map_or(offset + grapheme.len(), |(_, end)| end);
+            let combined = &value[offset..end];
+
+            // the tie bar sits on the first letter, the second one was already parsed
+            let tied = combined.contains(TIE_BARS);
+            let parsed = if tied {
+                Letter::from_grapheme(&combined.replace(TIE_BARS, ""))
+            } else {
+                Letter::from_grapheme(combined)
+            };
 
-            match Letter::try_from(combined.as_str()) {
+            match parsed {
+                Ok(letter) if tied => {
+                    let second = out.pop().ok_or(IpaParseError::DanglingTieBar { offset })?;
+                    out.push(Letter {
+                        ipa_type: LetterType::Affricate(Affricate {
+                            first: Box::new(letter),
+                            second: Box::new(second),
+                        }),
+                        diacritics: None,
+                    });
+                    failed = None;
+                }
                 Ok(letter) => {
                     out.push(letter);
-                    failed = vec![];
+                    failed = None;
                 }
-                Err(e) => {
-                    failed.push(grapheme);
-                    last_err = e;
+                Err(candidates) if candidates.len() > 1 => {
+                    return Err(grapheme_error(combined, offset, candidates));
                 }
+                Err(_) => failed = Some((offset, end)),
+            }
+        }
+        if let Some((offset, end)) = failed {
+            return Err(grapheme_error(&value[offset..end], offset, vec![]));
+        }
+        out.reverse();
+        out = join_tone_letters(out);
+        if options.untied_affricates {
+            out = join_untied_affricates(out);
+        }
+        Ok(Self(out))
+    }
+}
+
+// building the confusables table for every word would take longer than parsing it
+pub static DEFAULT_PARSE_OPTIONS: LazyLock<ParseOptions> = LazyLock::new(ParseOptions::default);
+
+impl TryFrom<&str> for Word {
+    type Error = IpaParseError;
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Self::parse(value, &DEFAULT_PARSE_OPTIONS)
+    }
+}
+
+// every tone letter is its own grapheme, a contour like ˥˩ is one suprasegmental
+fn join_tone_letters(letters: Vec<Letter>) -> Vec<Letter> {
+    let mut out: Vec<Letter> = vec![];
+    for letter in letters {
+        if let (
+            Some(Letter {
+                ipa_type: LetterType::Suprasegmental(Suprasegmental::ChaoToneLetter(last)),
+                diacritics: None,
+            }),
+            LetterType::Suprasegmental(Suprasegmental::ChaoToneLetter(tone)),
+        ) = (out.last_mut(), &letter.ipa_type)
+        {
+            if last.reversed == tone.reversed {
+                last.contour.extend(tone.contour.iter());
+                continue;
             }
         }
-        if failed.is_empty() {
-            out.reverse();
-            Ok(Self(out))
+        out.push(letter);
+    }
+    out
+}
+
+// only in front of a vowel, in a coda the pair is usually two segments like in the plural cats
+fn join_untied_affricates(letters: Vec<Letter>) -> Vec<Letter> {
+    let mut out: Vec<Letter> = vec![];
+    let mut letters = letters.into_iter().peekable();
+    while let Some(letter) = letters.next() {
+        let onset = letters
+            .peek()
+            .is_some_and(|next| matches!(next.ipa_type, LetterType::Vowel(_)));
+        if onset
+            && out
+                .last()
+                .is_some_and(|last| Affricate::is_untied_pair(last, &letter))
+        {
+            let first = out.pop().unwrap();
+            out.push(Letter {
+                ipa_type: LetterType::Affricate(Affricate {
+                    first: Box::new(first),
+                    second: Box::new(letter),
+                }),
+                diacritics: None,
+            });
         } else {
-            Err(last_err)
+            out.push(letter);
         }
     }
+    out
 }
 
 impl fmt::Display for Word {
@@ -1499,21 +2291,56 @@ impl fmt::Display for Word {
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Letter {
     pub ipa_type: LetterType,
     pub diacritics: Option<Vec<Diacritic>>,
 }
 
-impl TryFrom<&str> for Letter {
-    type Error = anyhow::Error;
-    fn try_from(value: &str) -> Result<Self, Self::Error> {
+impl Letter {
+    // vowels not marked as glides and consonants marked with the syllabic diacritic
+    pub fn is_syllabic(&self) -> bool {
+        match self.ipa_type {
+            LetterType::Vowel(_) => !self.is_glide(),
+            _ => self.has_diacritic(Diacritic::Syllabic),
+        }
+    }
+    // vowels marked as non-syllabic, like the ɪ̯ in aɪ̯
+    pub fn is_glide(&self) -> bool {
+        matches!(self.ipa_type, LetterType::Vowel(_)) && self.has_diacritic(Diacritic::NonSyllabic)
+    }
+    pub fn has_diacritic(&self, diacritic: Diacritic) -> bool {
+        self.diacritics
+            .as_ref()
+            .is_some_and(|x| x.contains(&diacritic))
+    }
+    pub fn sonority(&self) -> Option<u8> {
+        self.ipa_type.sonority()
+    }
+}
+
+impl Letter {
+    // the error holds every reading of an ambiguous grapheme, and is empty for unknown ones
+    fn from_grapheme(value: &str) -> Result<Self, Vec<LetterType>> {
         for (special, replace) in REPLACE_LIST {
             if value.contains(special) {
-                return Self::try_from(replace);
+                return Self::from_grapheme(&value.replace(special, replace));
             }
         }
-        let diacritics = get_diacritics(value);
-        let ipa_type = LetterType::try_from(value)?;
+        if let Ok(sup) = Suprasegmental::try_from(value) {
+            return Ok(Self {
+                ipa_type: LetterType::Suprasegmental(sup),
+                diacritics: None,
+            });
+        }
+        let grapheme = Grapheme::split(value);
+        let mut candidates = LetterType::candidates(&grapheme);
+        if candidates.len() != 1 {
+            return Err(candidates.into_iter().map(|(x, _)| x).collect());
+        }
+        let (ipa_type, chars) = candidates.remove(0);
+        // the marks that are part of the letter itself aren't diacritics
+        let diacritics = get_diacritics(&grapheme.remaining_marks(chars).unwrap_or_default());
         Ok(Self {
             ipa_type,
             diacritics: if diacritics.is_empty() {
@@ -1525,6 +2352,13 @@ impl TryFrom<&str> for Letter {
     }
 }
 
+impl TryFrom<&str> for Letter {
+    type Error = IpaParseError;
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Self::from_grapheme(value).map_err(|candidates| grapheme_error(value, 0, candidates))
+    }
+}
+
 impl fmt::Display for Letter {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let dia_str = if let Some(a) = &self.diacritics {
@@ -1538,30 +2372,81 @@ impl fmt::Display for Letter {
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum LetterType {
     PulmonicConsonant(PulmonicConsonant),
-    NonPulmonicConsonant,
+    NonPulmonicConsonant(NonPulmonicConsonant),
     Vowel(Vowel),
     Suprasegmental(Suprasegmental),
     MiscLetter(MiscLetter),
+    // two letters joined by a tie bar, also used for co-articulations like k͡p
+    Affricate(Affricate),
+}
+
+impl LetterType {
+    // rough sonority scale from plosives (1) to vowels (7), suprasegmentals have none
+    pub fn sonority(&self) -> Option<u8> {
+        match self {
+            LetterType::Vowel(_) => Some(7),
+            LetterType::PulmonicConsonant(consonant) => Some(match consonant.manner {
+                PulmonicConsonantManner::Plosive => 1,
+                PulmonicConsonantManner::SibilantFricative
+                | PulmonicConsonantManner::NonSibilantFricative
+                | PulmonicConsonantManner::LateralFricative => 3,
+                PulmonicConsonantManner::Nasal => 4,
+                PulmonicConsonantManner::Tap
+                | PulmonicConsonantManner::Trill
+                | PulmonicConsonantManner::LateralApproximant
+                | PulmonicConsonantManner::LateralTap => 5,
+                PulmonicConsonantManner::Approximant => match consonant.place {
+                    ConsonantPlace::Aveolar
+                    | ConsonantPlace::Postalveolar
+                    | ConsonantPlace::Retroflex => 5,
+                    _ => 6,
+                },
+            }),
+            LetterType::NonPulmonicConsonant(_) => Some(1),
+            LetterType::Affricate(_) => Some(2),
+            LetterType::MiscLetter(misc) => Some(match misc {
+                MiscLetter::SjSound => 3,
+                MiscLetter::VelarizedAveolar | MiscLetter::VoicedAlveolarLateralApproximant => 5,
+                _ => 6,
+            }),
+            LetterType::Suprasegmental(_) => None,
+        }
+    }
+}
+
+impl LetterType {
+    // the entries of all tables that take the most marks of the grapheme
+    fn candidates(grapheme: &Grapheme) -> Vec<(LetterType, &'static [char])> {
+        let mut best = BestEntries::default();
+        best.add(&VOWEL_LIST, &VOWEL_INDEX, grapheme, |x| {
+            LetterType::Vowel(x.clone())
+        });
+        best.add(
+            &NON_PULMONIC_CONSONANT_LIST,
+            &NON_PULMONIC_CONSONANT_INDEX,
+            grapheme,
+            |x| LetterType::NonPulmonicConsonant(x.clone()),
+        );
+        best.add(
+            &PULMONIC_CONSONANT_LIST,
+            &PULMONIC_CONSONANT_INDEX,
+            grapheme,
+            |x| LetterType::PulmonicConsonant(x.clone()),
+        );
+        best.add(&MISC_LETTER_LIST, &MISC_LETTER_INDEX, grapheme, |x| {
+            LetterType::MiscLetter(*x)
+        });
+        best.entries
+    }
 }
 
 impl TryFrom<&str> for LetterType {
-    type Error = anyhow::Error;
+    type Error = IpaParseError;
     fn try_from(value: &str) -> Result<Self, Self::Error> {
-        if let Ok(vow) = Vowel::try_from(value) {
-            return Ok(Self::Vowel(vow));
-        }
-        if let Ok(sup) = Suprasegmental::try_from(value) {
-            return Ok(Self::Suprasegmental(sup));
-        }
-        if let Ok(cons) = PulmonicConsonant::try_from(value) {
-            return Ok(Self::PulmonicConsonant(cons));
-        }
-        if let Ok(misc) = MiscLetter::try_from(value) {
-            return Ok(Self::MiscLetter(misc));
-        }
-        Err(anyhow!("can't construct LetterType from {}", value))
+        Ok(Letter::try_from(value)?.ipa_type)
     }
 }
 
@@ -1573,15 +2458,53 @@ impl fmt::Display for LetterType {
             match self {
                 LetterType::Suprasegmental(s) => s.to_string(),
                 LetterType::PulmonicConsonant(p) => p.to_string(),
+                LetterType::NonPulmonicConsonant(n) => n.to_string(),
                 LetterType::Vowel(v) => v.to_string(),
                 LetterType::MiscLetter(m) => m.to_string(),
-                _ => return Err(fmt::Error),
+                LetterType::Affricate(a) => a.to_string(),
             }
         )
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Affricate {
+    pub first: Box<Letter>,
+    pub second: Box<Letter>,
+}
+
+impl Affricate {
+    // plosive followed by a homorganic sibilant of the same voicing, e.g. tʃ, dʒ, ts, ʈʂ
+    pub fn is_untied_pair(first: &Letter, second: &Letter) -> bool {
+        let (LetterType::PulmonicConsonant(stop), LetterType::PulmonicConsonant(fricative)) =
+            (&first.ipa_type, &second.ipa_type)
+        else {
+            return false;
+        };
+        stop.manner == PulmonicConsonantManner::Plosive
+            && fricative.manner == PulmonicConsonantManner::SibilantFricative
+            && stop.voicing == fricative.voicing
+            && matches!(
+                (&stop.place, &fricative.place),
+                (
+                    ConsonantPlace::Aveolar,
+                    ConsonantPlace::Aveolar
+                        | ConsonantPlace::Postalveolar
+                        | ConsonantPlace::Palatal
+                ) | (ConsonantPlace::Retroflex, ConsonantPlace::Retroflex)
+            )
+    }
+}
+
+impl fmt::Display for Affricate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}{}", self.first, TIE_BARS[0], self.second)
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum MiscLetter {
     VoicedLabialPalatalApproximant,
     VoicelessLabialPalatalApproximant,
@@ -1606,18 +2529,20 @@ impl std::fmt::Display for MiscLetter {
     }
 }
 impl TryFrom<&str> for MiscLetter {
-    type Error = ();
+    type Error = IpaParseError;
     fn try_from(value: &str) -> Result<Self, Self::Error> {
-        for (i, j) in MISC_LETTER_LIST.iter() {
-            if j.iter().all(|x| value.contains(*x)) {
-                return Ok(*i);
-            }
-        }
-        Err(())
+        unique_entry(
+            &MISC_LETTER_LIST,
+            &MISC_LETTER_INDEX,
+            value,
+            LetterCategory::MiscLetter,
+            |x| LetterType::MiscLetter(*x),
+        )
     }
 }
 
 #[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Diacritic {
     Syllabic,
     NonSyllabic,
@@ -1653,12 +2578,14 @@ pub enum Diacritic {
     RetractedTongueRoot,
     Nasalized,
     Rhoticity,
+    Pitch(PitchDiacritic),
 }
 
-fn get_diacritics(value: &str) -> Vec<Diacritic> {
+// in the order they appear in marks
+fn get_diacritics(marks: &[char]) -> Vec<Diacritic> {
     let mut diacs = vec![];
-    for (entry, dia) in DIACRITIC_MAP.entries() {
-        if value.contains(*entry) {
+    for dia in marks.iter().filter_map(|c| DIACRITIC_MAP.get(c)) {
+        if !diacs.contains(dia) {
             diacs.push(*dia);
         }
     }
@@ -1675,6 +2602,7 @@ impl fmt::Display for Diacritic {
     }
 }
 #[derive(PartialEq, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Suprasegmental {
     PrimaryStress,
     SecondaryStress,
@@ -1689,13 +2617,15 @@ pub enum Suprasegmental {
     GlobalFall,
     Upstep,
     Downstep,
-    PitchDiacritic(PitchDiacritic),
     ChaoToneLetter(ChaoToneLetter),
 }
 
 impl fmt::Display for Suprasegmental {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        if let Some((text, _)) = SUPRASEGREMENTAL_MAP.entries().find(|(_, sup)| *sup == self) {
+        if let Suprasegmental::ChaoToneLetter(tone) = self {
+            write!(f, "{}", tone)
+        } else if let Some((text, _)) = SUPRASEGREMENTAL_MAP.entries().find(|(_, sup)| *sup == self)
+        {
             write!(f, "{}", text)
         } else {
             Err(fmt::Error)
@@ -1704,14 +2634,23 @@ impl fmt::Display for Suprasegmental {
 }
 
 impl TryFrom<&str> for Suprasegmental {
-    type Error = ();
+    type Error = IpaParseError;
     fn try_from(value: &str) -> Result<Self, Self::Error> {
-        for i in value.chars() {
-            if let Some(a) = SUPRASEGREMENTAL_MAP.get(&i) {
-                return Ok(a.clone());
-            }
+        if let Ok(tone) = ChaoToneLetter::try_from(value) {
+            return Ok(Self::ChaoToneLetter(tone));
+        }
+        let mut chars = value.chars();
+        match (
+            chars.next().and_then(|c| SUPRASEGREMENTAL_MAP.get(&c)),
+            chars.next(),
+        ) {
+            (Some(sup), None) => Ok(sup.clone()),
+            _ => Err(IpaParseError::UnknownGrapheme {
+                grapheme: value.to_string(),
+                offset: 0,
+                tried: vec![LetterCategory::Suprasegmental],
+            }),
         }
-        Err(())
     }
 }
 
@@ -1729,24 +2668,68 @@ impl std::fmt::Display for Vowel {
     }
 }
 impl TryFrom<&str> for Vowel {
-    type Error = ();
+    type Error = IpaParseError;
     fn try_from(value: &str) -> Result<Self, Self::Error> {
-        for (i, j) in VOWEL_LIST.iter() {
-            if j.iter().all(|x| value.contains(*x)) {
-                return Ok(i.clone());
-            }
-        }
-        Err(())
+        unique_entry(
+            &VOWEL_LIST,
+            &VOWEL_INDEX,
+            value,
+            LetterCategory::Vowel,
+            |x| LetterType::Vowel(x.clone()),
+        )
     }
 }
 
 #[derive(PartialEq, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ChaoToneLetter {
-    contour: Vec<ChaoToneLetterHeight>,
-    reversed: bool,
+    pub contour: Vec<ChaoToneLetterHeight>,
+    pub reversed: bool,
+}
+
+impl fmt::Display for ChaoToneLetter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for height in self.contour.iter() {
+            let Some((text, _)) = CHAO_TONE_LETTER_MAP
+                .entries()
+                .find(|(_, tone)| **tone == (*height, self.reversed))
+            else {
+                return Err(fmt::Error);
+            };
+            write!(f, "{}", text)?;
+        }
+        Ok(())
+    }
+}
+
+impl TryFrom<&str> for ChaoToneLetter {
+    type Error = IpaParseError;
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        // the offset is that of the tone letter that doesn't fit
+        let error = |offset| IpaParseError::UnknownGrapheme {
+            grapheme: value.to_string(),
+            offset,
+            tried: vec![LetterCategory::Suprasegmental],
+        };
+        let mut contour = vec![];
+        let mut reversed = None;
+        for (offset, c) in value.char_indices() {
+            let (height, rev) = CHAO_TONE_LETTER_MAP.get(&c).ok_or_else(|| error(offset))?;
+            // normal and reversed tone letters can't be mixed in one contour
+            if *reversed.get_or_insert(*rev) != *rev {
+                return Err(error(offset));
+            }
+            contour.push(*height);
+        }
+        match reversed {
+            Some(reversed) => Ok(Self { contour, reversed }),
+            None => Err(error(0)),
+        }
+    }
 }
 
 #[derive(PartialEq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ChaoToneLetterHeight {
     ExtraHigh,
     High,
@@ -1756,6 +2739,7 @@ pub enum ChaoToneLetterHeight {
 }
 
 #[derive(PartialEq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PitchDiacritic {
     ExtraHigh,
     High,
@@ -1773,6 +2757,7 @@ pub enum PitchDiacritic {
 }
 
 #[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Vowel {
     pub height: VowelHeight,
     pub backness: VowelBackness,
@@ -1780,6 +2765,7 @@ pub struct Vowel {
 }
 
 #[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum VowelHeight {
     Close,
     NearClose,
@@ -1791,18 +2777,21 @@ pub enum VowelHeight {
 }
 
 #[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum VowelBackness {
     Front,
     Central,
     Back,
 }
 #[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum VowelRoundedness {
     Unrounded,
     Rounded,
 }
 
 #[derive(PartialEq, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PulmonicConsonant {
     pub manner: PulmonicConsonantManner,
     pub place: ConsonantPlace,
@@ -1825,18 +2814,63 @@ impl std::fmt::Display for PulmonicConsonant {
     }
 }
 impl TryFrom<&str> for PulmonicConsonant {
-    type Error = ();
+    type Error = IpaParseError;
     fn try_from(value: &str) -> Result<Self, Self::Error> {
-        for (i, j) in PULMONIC_CONSONANT_LIST.iter() {
-            if j.iter().all(|x| value.contains(*x)) {
-                return Ok(i.clone());
-            }
+        unique_entry(
+            &PULMONIC_CONSONANT_LIST,
+            &PULMONIC_CONSONANT_INDEX,
+            value,
+            LetterCategory::PulmonicConsonant,
+            |x| LetterType::PulmonicConsonant(x.clone()),
+        )
+    }
+}
+
+#[derive(PartialEq, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NonPulmonicConsonant {
+    pub kind: NonPulmonicConsonantKind,
+    pub place: ConsonantPlace,
+}
+
+#[derive(PartialEq, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum NonPulmonicConsonantKind {
+    Click,
+    LateralClick,
+    Implosive,
+    // ejectives exist for plosives and the different fricatives
+    Ejective(PulmonicConsonantManner),
+}
+
+impl std::fmt::Display for NonPulmonicConsonant {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(a) = NON_PULMONIC_CONSONANT_LIST
+            .iter()
+            .find(|(consonant, _)| self == consonant)
+            .map(|(_, chars)| chars.iter().collect::<String>())
+        {
+            write!(f, "{}", a)
+        } else {
+            Err(fmt::Error)
         }
-        Err(())
+    }
+}
+impl TryFrom<&str> for NonPulmonicConsonant {
+    type Error = IpaParseError;
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        unique_entry(
+            &NON_PULMONIC_CONSONANT_LIST,
+            &NON_PULMONIC_CONSONANT_INDEX,
+            value,
+            LetterCategory::NonPulmonicConsonant,
+            |x| LetterType::NonPulmonicConsonant(x.clone()),
+        )
     }
 }
 
 #[derive(PartialEq, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PulmonicConsonantManner {
     Nasal,
     Plosive,
@@ -1851,6 +2885,7 @@ pub enum PulmonicConsonantManner {
 }
 
 #[derive(PartialEq, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ConsonantPlace {
     Bilabial,
     Labiodental,
@@ -1867,6 +2902,7 @@ pub enum ConsonantPlace {
 }
 
 #[derive(PartialEq, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ConsonantVoicing {
     Voiced,
     Voiceless,
@@ -1946,4 +2982,391 @@ mod tests {
             String::from("\u{030A}\u{0279}\u{0331}\u{02D4}")
         );
     }
+    #[test]
+    fn non_pul_cons_from_str() {
+        let click = NonPulmonicConsonant::try_from("\u{01C1}").unwrap();
+        assert_eq!(
+            click,
+            NonPulmonicConsonant {
+                kind: NonPulmonicConsonantKind::LateralClick,
+                place: ConsonantPlace::Aveolar,
+            }
+        );
+        let implosive = NonPulmonicConsonant::try_from("\u{0253}").unwrap();
+        assert_eq!(
+            implosive,
+            NonPulmonicConsonant {
+                kind: NonPulmonicConsonantKind::Implosive,
+                place: ConsonantPlace::Bilabial,
+            }
+        );
+    }
+    #[test]
+    fn ejective_letter() {
+        let letter = Letter::try_from("k\u{02BC}").unwrap();
+        assert_eq!(
+            letter.ipa_type,
+            LetterType::NonPulmonicConsonant(NonPulmonicConsonant {
+                kind: NonPulmonicConsonantKind::Ejective(PulmonicConsonantManner::Plosive),
+                place: ConsonantPlace::Velar,
+            })
+        );
+        assert_eq!(letter.to_string(), "k\u{02BC}");
+    }
+    #[test]
+    fn word_with_non_pulmonic() {
+        let input = "ǃoɓa tʼi sʼa";
+        for word in input.split(' ') {
+            assert_eq!(Word::try_from(word).unwrap().to_string(), word);
+        }
+        let word = Word::try_from("tʼi").unwrap();
+        assert_eq!(word.0.len(), 2);
+    }
+    #[test]
+    fn tied_affricate() {
+        let word = Word::try_from("t\u{0361}ʃʰɝt\u{035C}ʃ").unwrap();
+        assert_eq!(word.0.len(), 3);
+        let LetterType::Affricate(affricate) = &word.0[0].ipa_type else {
+            panic!("expected affricate, got {:?}", word.0[0]);
+        };
+        assert_eq!(affricate.first.to_string(), "t");
+        assert_eq!(affricate.second.to_string(), "ʃʰ");
+        // tie bar below is rendered as the canonical tie bar above
+        assert_eq!(word.to_string(), "t\u{0361}ʃʰɜ\u{02DE}t\u{0361}ʃ");
+    }
+    #[test]
+    fn untied_affricate() {
+        assert_eq!(Word::try_from("dʒʌdʒ").unwrap().0.len(), 5);
+
+        let options = ParseOptions {
+            untied_affricates: true,
+            ..Default::default()
+        };
+        let word = Word::parse("dʒʌdʒɪz", &options).unwrap();
+        assert_eq!(word.0.len(), 5);
+        assert_eq!(word.to_string(), "d\u{0361}ʒʌd\u{0361}ʒɪz");
+        assert_eq!(Word::parse("ɛkspɹ", &options).unwrap().0.len(), 5);
+        // plurals and possessives keep their coda clusters
+        assert_eq!(Word::parse("kæts", &options).unwrap().0.len(), 4);
+        assert_eq!(Word::parse("bɛdz", &options).unwrap().0.len(), 4);
+    }
+    #[test]
+    fn diacritic_order() {
+        let letter = Letter::try_from("a\u{0330}ʰ").unwrap();
+        assert_eq!(
+            letter.diacritics,
+            Some(vec![Diacritic::CreakyVoiced, Diacritic::Aspirated])
+        );
+        // failed graphemes are combined in input order
+        let word = Word::try_from("ka\u{0330}ʰ").unwrap();
+        assert_eq!(word.0[1], letter);
+    }
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            Word::try_from("%kæt"),
+            Err(IpaParseError::UnknownGrapheme {
+                grapheme: "%".to_string(),
+                offset: 0,
+                tried: vec![],
+            })
+        );
+        // a vowel, but not with a macron below
+        assert_eq!(
+            Word::try_from("a\u{0331}k"),
+            Err(IpaParseError::UnknownGrapheme {
+                grapheme: "a\u{0331}".to_string(),
+                offset: 0,
+                tried: vec![LetterCategory::Vowel],
+            })
+        );
+        assert_eq!(
+            Vowel::try_from("k"),
+            Err(IpaParseError::UnknownGrapheme {
+                grapheme: "k".to_string(),
+                offset: 0,
+                tried: vec![LetterCategory::Vowel],
+            })
+        );
+        assert!(matches!(
+            PulmonicConsonant::try_from("ɾ\u{033C}\u{0325}"),
+            Err(IpaParseError::AmbiguousGrapheme { .. })
+        ));
+        assert_eq!(
+            Letter::try_from("a\u{0331}"),
+            Err(IpaParseError::UnknownGrapheme {
+                grapheme: "a\u{0331}".to_string(),
+                offset: 0,
+                tried: vec![LetterCategory::Vowel],
+            })
+        );
+        assert!(matches!(
+            LetterType::try_from("ɾ\u{033C}\u{0325}"),
+            Err(IpaParseError::AmbiguousGrapheme { .. })
+        ));
+        assert_eq!(
+            Word::try_from("[k\u{0361}]"),
+            Err(IpaParseError::DanglingTieBar { offset: 1 })
+        );
+    }
+    #[test]
+    fn exact_base_letters() {
+        let letter = Letter::try_from("m\u{0325}").unwrap();
+        assert_eq!(letter.diacritics, None);
+        assert_eq!(letter.to_string(), "m\u{0325}");
+        assert_eq!(
+            Letter::try_from("ɱ\u{030A}").unwrap().ipa_type,
+            LetterType::PulmonicConsonant(PulmonicConsonant {
+                manner: PulmonicConsonantManner::Nasal,
+                place: ConsonantPlace::Labiodental,
+                voicing: ConsonantVoicing::Voiceless,
+            })
+        );
+        assert_eq!(
+            Vowel::try_from("ɤ").unwrap().roundedness,
+            VowelRoundedness::Unrounded
+        );
+        assert_eq!(
+            MiscLetter::try_from("w"),
+            Ok(MiscLetter::VoicedLabialVelarApproximant)
+        );
+        // marks that are neither part of a letter nor a diacritic aren't dropped silently
+        assert!(Letter::try_from("a\u{0331}").is_err());
+        assert!(Suprasegmental::try_from("aː").is_err());
+    }
+    #[test]
+    fn ambiguous_grapheme() {
+        // both ɾ̼ and ɾ̥ are letters, but there is no voiceless linguolabial tap
+        let Err(IpaParseError::AmbiguousGrapheme {
+            offset, candidates, ..
+        }) = Word::try_from("aɾ\u{033C}\u{0325}")
+        else {
+            panic!("expected an ambiguous grapheme");
+        };
+        assert_eq!(offset, 1);
+        assert_eq!(candidates.len(), 2);
+    }
+    #[test]
+    fn every_entry_with_every_diacritic() {
+        let entries = VOWEL_LIST
+            .iter()
+            .map(|x| x.1)
+            .chain(PULMONIC_CONSONANT_LIST.iter().map(|x| x.1))
+            .chain(NON_PULMONIC_CONSONANT_LIST.iter().map(|x| x.1))
+            .chain(MISC_LETTER_LIST.iter().map(|x| x.1));
+        for chars in entries {
+            let entry: String = chars.iter().collect();
+            // also fails if two entries are written the same or have the same value
+            let letter = Letter::try_from(entry.as_str()).unwrap();
+            assert_eq!(letter.diacritics, None, "{entry}");
+            assert_eq!(letter.to_string(), entry);
+
+            for (mark, diacritic) in DIACRITIC_MAP.entries() {
+                let input = format!("{entry}{mark}");
+                let with = match Letter::from_grapheme(&input) {
+                    Ok(with) => with,
+                    // only letters that come with marks of their own can become ambiguous
+                    Err(candidates) => {
+                        assert!(candidates.len() > 1, "{input}");
+                        assert!(chars.iter().any(|c| is_mark(*c)), "{input}");
+                        continue;
+                    }
+                };
+                let output = with.to_string();
+                let output = Grapheme::split(&output);
+                assert_eq!(output.base, Grapheme::split(&entry).base, "{input}");
+                // every mark is kept exactly once
+                assert_eq!(
+                    output.marks.len(),
+                    Grapheme::split(&input).marks.len(),
+                    "{input}"
+                );
+                assert!(
+                    with.ipa_type.to_string().contains(*mark) || with.has_diacritic(*diacritic),
+                    "{input}"
+                );
+            }
+        }
+    }
+    #[test]
+    fn syllable_stress() {
+        let word = Word::try_from("ˌɪntɚˈnæʃənəɫ").unwrap();
+        let syls = syls_from_word(&word, &english::EnglishSyllableRule::default());
+        let stresses: Vec<_> = syls.iter().map(|x| x.stress).collect();
+        assert_eq!(
+            stresses,
+            [
+                SyllableStress::Secondary,
+                SyllableStress::Unstressed,
+                SyllableStress::Primary,
+                SyllableStress::Unstressed,
+                SyllableStress::Unstressed,
+            ]
+        );
+        // stress marks aren't part of the onset anymore
+        assert_eq!(syls[2].onset.len(), 1);
+        assert_eq!(syls[2].to_string(), "ˈnæ");
+    }
+    #[test]
+    fn syllable_length() {
+        let word = Word::try_from("ˈstuːdi̯ʊm").unwrap();
+        let syls = syls_from_word(&word, &english::EnglishSyllableRule::default());
+        assert_eq!(syls[0].length, VowelLength::Long);
+        assert_eq!(syls[0].nucleus.len(), 1);
+        assert!(syls[0].coda.is_empty());
+        assert_eq!(syls[0].to_string(), "ˈstuː");
+        assert_eq!(syls[1].length, VowelLength::Short);
+
+        // long vowels don't form diphthongs with the next vowel
+        let word = Word::try_from("eːɪ").unwrap();
+        assert_eq!(
+            syls_from_word(&word, &english::EnglishSyllableRule::default()).len(),
+            2
+        );
+    }
+    #[test]
+    fn rule_by_language() {
+        assert!(syllable_rule_for_language("de-AT").is_some());
+        assert!(syllable_rule_for_language("xx").is_none());
+        let i = Letter::try_from("ɪ").unwrap();
+        let schwa = Letter::try_from("ə").unwrap();
+        assert!(syllable_rule_for_language("en_GB")
+            .unwrap()
+            .is_diphthong(&[i.clone(), schwa.clone()]));
+        assert!(!syllable_rule_for_language("en")
+            .unwrap()
+            .is_diphthong(&[i, schwa]));
+    }
+    #[test]
+    fn leading_letters_join_first_onset() {
+        // ts and ŋ can't start an english onset, but no letter may get lost
+        let word = Word::try_from("ˈtsunami").unwrap();
+        let syls = syls_from_word(&word, &english::EnglishSyllableRule::default());
+        let syls: Vec<String> = syls.iter().map(|x| x.to_string()).collect();
+        assert_eq!(syls, ["ˈtsu", "na", "mi"]);
+        let word = Word::try_from("ˈŋa").unwrap();
+        let syls = syls_from_word(&word, &english::EnglishSyllableRule::default());
+        assert_eq!(syls.len(), 1);
+        assert_eq!(syls[0].stress, SyllableStress::Primary);
+        assert_eq!(syls[0].to_string(), "ˈŋa");
+        // ʊ̯ isn't a glide english onsets can end in
+        let word = Word::try_from("ˈkʊ̯ɪk").unwrap();
+        let syls = syls_from_word(&word, &english::EnglishSyllableRule::default());
+        assert_eq!(syls.len(), 1);
+        assert_eq!(syls[0].to_string(), "ˈkʊ\u{0311}ɪk");
+    }
+    // one consonant onsets, only nasals close a syllable
+    #[derive(Default)]
+    struct HookRule {
+        ambisyllabic: bool,
+        resyllabify: bool,
+    }
+    impl SyllableRule for HookRule {
+        fn makes_valid_onset(&self, new: &Letter, rest: &[Letter]) -> bool {
+            onset_with(new, rest).len() <= 1
+        }
+        fn is_diphthong(&self, _nucleus: &[Letter]) -> bool {
+            false
+        }
+        fn makes_valid_coda(&self, new: &Letter, rest: &[Letter]) -> bool {
+            rest.is_empty() && new.sonority() == Some(4)
+        }
+        fn is_ambisyllabic(&self, before: &Syllable, _after: &Syllable) -> bool {
+            self.ambisyllabic && before.stress == SyllableStress::Primary && before.coda.is_empty()
+        }
+        fn resyllabify(&self, before: &Syllable, _after: &Syllable) -> usize {
+            (self.resyllabify && before.stress == SyllableStress::Primary) as usize
+        }
+    }
+    #[test]
+    fn syllable_rule_hooks() {
+        let syls = |x, rule: HookRule| -> Vec<String> {
+            syls_from_word(&Word::try_from(x).unwrap(), &rule)
+                .iter()
+                .map(|x| x.to_string())
+                .collect()
+        };
+        assert_eq!(syls("ˈbanta", HookRule::default()), ["ˈban", "ta"]);
+        // s can't be a coda, so the onset takes it anyway
+        assert_eq!(syls("ˈbasta", HookRule::default()), ["ˈba", "sta"]);
+        // neither s nor nt is allowed, the default split is kept
+        assert_eq!(syls("ˈbasnta", HookRule::default()), ["ˈbasn", "ta"]);
+        assert_eq!(syls("ˈsɪti", HookRule::default()), ["ˈsɪ", "ti"]);
+        let ambisyllabic = HookRule {
+            ambisyllabic: true,
+            ..Default::default()
+        };
+        assert_eq!(syls("ˈsɪti", ambisyllabic), ["ˈsɪt", "ti"]);
+        let resyllabify = HookRule {
+            resyllabify: true,
+            ..Default::default()
+        };
+        assert_eq!(syls("ˈsɪti", resyllabify), ["ˈsɪt", "i"]);
+    }
+    #[test]
+    fn syllable_weight() {
+        let weights = |x| -> Vec<(usize, SyllableWeight)> {
+            syls_from_word(
+                &Word::try_from(x).unwrap(),
+                &english::EnglishSyllableRule::default(),
+            )
+            .iter()
+            .map(|x| (x.morae(), x.weight()))
+            .collect()
+        };
+        use SyllableWeight::*;
+        assert_eq!(weights("ˈsɪti"), [(1, Light), (1, Light)]);
+        assert_eq!(weights("ˈbiːt"), [(3, Superheavy)]);
+        assert_eq!(weights("ˈtaɪm"), [(3, Superheavy)]);
+        assert_eq!(weights("ˈkæt"), [(2, Heavy)]);
+        assert_eq!(weights("ˈbiː"), [(2, Heavy)]);
+        assert_eq!(weights("ˈhæmstɝ")[0], (2, Heavy));
+        assert_eq!(weights("ˈbʌtn̩")[1], (1, Light));
+    }
+    #[test]
+    fn chao_tone_letters() {
+        let word = Word::try_from("ma˥˩").unwrap();
+        assert_eq!(word.0.len(), 3);
+        assert_eq!(
+            word.0[2].ipa_type,
+            LetterType::Suprasegmental(Suprasegmental::ChaoToneLetter(ChaoToneLetter {
+                contour: vec![
+                    ChaoToneLetterHeight::ExtraHigh,
+                    ChaoToneLetterHeight::ExtraLow
+                ],
+                reversed: false,
+            }))
+        );
+        assert_eq!(word.to_string(), "ma˥˩");
+
+        let reversed = Word::try_from("꜒꜔ma").unwrap();
+        assert_eq!(reversed.0.len(), 3);
+        assert_eq!(reversed.to_string(), "꜒꜔ma");
+        // normal and reversed tone letters can't be mixed
+        assert_eq!(
+            ChaoToneLetter::try_from("˥꜔"),
+            Err(IpaParseError::UnknownGrapheme {
+                grapheme: "˥꜔".to_string(),
+                offset: 2,
+                tried: vec![LetterCategory::Suprasegmental],
+            })
+        );
+        assert!(ChaoToneLetter::try_from("").is_err());
+    }
+    #[test]
+    fn pitch_diacritics() {
+        let letter = Letter::try_from("a\u{030B}").unwrap();
+        assert_eq!(
+            letter.diacritics,
+            Some(vec![Diacritic::Pitch(PitchDiacritic::ExtraHigh)])
+        );
+        assert_eq!(letter.to_string(), "a\u{030B}");
+
+        let word = Word::try_from("o\u{0301}ko\u{0300}").unwrap();
+        assert_eq!(word.to_string(), "o\u{0301}ko\u{0300}");
+        assert_eq!(
+            word.0[2].diacritics,
+            Some(vec![Diacritic::Pitch(PitchDiacritic::Low)])
+        );
+    }
 }
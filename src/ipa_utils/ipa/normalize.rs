@@ -0,0 +1,202 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+use unicode_normalization::{is_nfd_quick, IsNormalized, UnicodeNormalization};
+
+// characters that look like ipa but aren't, mapped to what was most likely meant
+pub const CONFUSABLE_LIST: [(char, &str); 31] = [
+    // ascii
+    ('g', "ɡ"),
+    (':', "ː"),
+    ('\'', "ˈ"),
+    // punctuation used as stress or length marks
+    ('\u{2019}', "ˈ"),
+    ('\u{02B9}', "ˈ"),
+    ('\u{02BB}', "ˈ"),
+    ('\u{A789}', "ː"),
+    ('\u{02F8}', "ː"),
+    // greek
+    ('\u{03B5}', "ɛ"),
+    ('\u{03B1}', "ɑ"),
+    ('\u{03B3}', "ɣ"),
+    ('\u{03C6}', "ɸ"),
+    ('\u{03C5}', "ʊ"),
+    ('\u{03B9}', "ɪ"),
+    // cyrillic
+    ('\u{0430}', "a"),
+    ('\u{0435}', "e"),
+    ('\u{043E}', "o"),
+    ('\u{0440}', "p"),
+    ('\u{0441}', "c"),
+    ('\u{0445}', "x"),
+    ('\u{0443}', "y"),
+    ('\u{0456}', "i"),
+    ('\u{0458}', "j"),
+    ('\u{04D9}', "ə"),
+    ('\u{0454}', "ɛ"),
+    // ligatures that were removed from the ipa
+    ('ʧ', "t\u{0361}ʃ"),
+    ('ʤ', "d\u{0361}ʒ"),
+    ('ʦ', "t\u{0361}s"),
+    ('ʣ', "d\u{0361}z"),
+    ('ʨ', "t\u{0361}ɕ"),
+    ('ʥ', "d\u{0361}ʑ"),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubstitutionKind {
+    // precomposed letters like é, split into letter and diacritic
+    Decomposed,
+    Confusable,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Substitution {
+    // byte offset in the input
+    pub offset: usize,
+    pub from: char,
+    pub to: String,
+    pub kind: SubstitutionKind,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Normalizer {
+    pub confusables: HashMap<char, String>,
+}
+
+impl Default for Normalizer {
+    fn default() -> Self {
+        Self {
+            confusables: CONFUSABLE_LIST
+                .iter()
+                .map(|(from, to)| (*from, to.to_string()))
+                .collect(),
+        }
+    }
+}
+
+impl Normalizer {
+    fn confusable(&self, c: char) -> Option<&str> {
+        self.confusables.get(&c).map(|to| to.as_str())
+    }
+
+    // NFD plus the confusables, with every substitution that was made
+    pub fn normalize(&self, value: &str) -> (String, Vec<Substitution>) {
+        let mut out = String::with_capacity(value.len());
+        let mut substitutions = vec![];
+        for (offset, c) in value.char_indices() {
+            let (to, kind) = if let Some(to) = self.confusable(c) {
+                (to.to_string(), SubstitutionKind::Confusable)
+            } else {
+                (c.nfd().collect::<String>(), SubstitutionKind::Decomposed)
+            };
+            if to.chars().ne([c]) {
+                substitutions.push(Substitution {
+                    offset,
+                    from: c,
+                    to: to.clone(),
+                    kind,
+                });
+            }
+            out += &to;
+        }
+        // also brings stacked diacritics into canonical order
+        (out.nfd().collect(), substitutions)
+    }
+
+    // only allocates if there is something to change
+    pub fn normalize_str<'a>(&self, value: &'a str) -> Cow<'a, str> {
+        let unchanged = is_nfd_quick(value.chars()) == IsNormalized::Yes
+            && !value.chars().any(|c| self.confusable(c).is_some());
+        if unchanged {
+            Cow::Borrowed(value)
+        } else {
+            Cow::Owned(self.normalize(value).0)
+        }
+    }
+}
+
+// the byte offset in the input of the character that became the one at offset in the output
+pub fn input_offset(value: &str, substitutions: &[Substitution], offset: usize) -> usize {
+    let mut substitutions = substitutions.iter().peekable();
+    let mut end = 0;
+    for (i, c) in value.char_indices() {
+        end += match substitutions.next_if(|x| x.offset == i) {
+            Some(substitution) => substitution.to.len(),
+            None => c.len_utf8(),
+        };
+        if end > offset {
+            return i;
+        }
+    }
+    value.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ipa_utils::ipa::{IpaParseError, Word};
+
+    #[test]
+    fn report_substitutions() {
+        let (text, substitutions) = Normalizer::default().normalize("'gʊd:é");
+        assert_eq!(text, "ˈɡʊdːe\u{0301}");
+        let kinds: Vec<_> = substitutions.iter().map(|x| (x.offset, x.kind)).collect();
+        assert_eq!(
+            kinds,
+            [
+                (0, SubstitutionKind::Confusable),
+                (1, SubstitutionKind::Confusable),
+                (5, SubstitutionKind::Confusable),
+                (6, SubstitutionKind::Decomposed),
+            ]
+        );
+        assert!(Normalizer::default().normalize("ˈɡʊd").1.is_empty());
+    }
+
+    #[test]
+    fn parse_lookalikes() {
+        assert_eq!(
+            Word::try_from("'gʊd").unwrap(),
+            Word::try_from("ˈɡʊd").unwrap()
+        );
+        // cyrillic а and greek ε
+        assert_eq!(
+            Word::try_from("k\u{0430}t\u{03B5}").unwrap(),
+            Word::try_from("katɛ").unwrap()
+        );
+        assert_eq!(
+            Word::try_from("ʧɝʧ").unwrap(),
+            Word::try_from("t͡ʃɝt͡ʃ").unwrap()
+        );
+        // precomposed ã
+        assert_eq!(
+            Word::try_from("\u{00E3}").unwrap(),
+            Word::try_from("a\u{0303}").unwrap()
+        );
+    }
+
+    #[test]
+    fn error_offsets_in_input() {
+        // ʧ is written as three characters in the normalized text
+        let Err(IpaParseError::AmbiguousGrapheme { offset, .. }) =
+            Word::try_from("ʧaɾ\u{033C}\u{0325}")
+        else {
+            panic!("expected an ambiguous grapheme");
+        };
+        assert_eq!(offset, 3);
+        let (_, substitutions) = Normalizer::default().normalize("'ʧa");
+        assert_eq!(input_offset("'ʧa", &substitutions, 0), 0);
+        assert_eq!(input_offset("'ʧa", &substitutions, 4), 1);
+        assert_eq!(input_offset("'ʧa", &substitutions, 7), 3);
+    }
+
+    #[test]
+    fn custom_table() {
+        let normalizer = Normalizer {
+            confusables: HashMap::from([('R', "ʁ".to_string())]),
+        };
+        assert_eq!(normalizer.normalize_str("Ra:"), "ʁa:");
+        assert!(matches!(normalizer.normalize_str("ʁa"), Cow::Borrowed(_)));
+    }
+}
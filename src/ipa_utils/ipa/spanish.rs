@@ -0,0 +1,96 @@
+use crate::ipa_utils::ipa::inventory::ClusterList;
+use crate::ipa_utils::ipa::*;
+use std::sync::LazyLock;
+
+#[derive(Debug, Clone, Default)]
+pub struct SpanishSyllableRule {}
+
+// see https://en.wikipedia.org/wiki/Spanish_phonology
+
+// with the approximant allophones of b, d and ɡ, as most transcriptions are narrow
+const SPANISH_CONSONANT_LIST: [&str; 25] = [
+    "p", "b", "β", "t", "d", "ð", "k", "ɡ", "ɣ", "f", "θ", "s", "x", "ʝ", "ʎ", "t͡ʃ", "m", "n", "ɲ",
+    "ŋ", "l", "ɾ", "r", "j", "w",
+];
+
+const SPANISH_VOWEL_LIST: [&str; 5] = ["i", "e", "a", "o", "u"];
+
+// falling and rising diphthongs when the glide is written as a vowel, triphthongs like in
+// buey follow from these
+const SPANISH_DIPHTHONG_LIST: [&str; 14] = [
+    "ai", "ei", "oi", "au", "eu", "ou", "ia", "ie", "io", "iu", "ua", "ue", "ui", "uo",
+];
+
+// an obstruent and a liquid, s clusters are split like in es.pa.ɲa
+const SPANISH_ONSET_LIST: [&str; 17] = [
+    "pl", "pɾ", "bl", "bɾ", "βl", "βɾ", "fl", "fɾ", "tɾ", "dɾ", "ðɾ", "kl", "kɾ", "ɡl", "ɡɾ", "ɣl",
+    "ɣɾ",
+];
+
+const SPANISH_GLIDE_LIST: [&str; 2] = ["j", "w"];
+
+static SPANISH_INVENTORY: LazyLock<PhonemeInventory> = LazyLock::new(|| {
+    PhonemeInventory::new(
+        &SPANISH_CONSONANT_LIST,
+        &SPANISH_VOWEL_LIST,
+        &SPANISH_DIPHTHONG_LIST,
+    )
+    .expect("spanish inventory is valid ipa")
+});
+static SPANISH_ONSETS: LazyLock<ClusterList> =
+    LazyLock::new(|| ClusterList::new(&SPANISH_ONSET_LIST).expect("spanish onsets are valid ipa"));
+static SPANISH_GLIDES: LazyLock<ClusterList> =
+    LazyLock::new(|| ClusterList::new(&SPANISH_GLIDE_LIST).expect("spanish glides are valid ipa"));
+
+impl SyllableRule for SpanishSyllableRule {
+    fn makes_valid_onset(&self, new: &Letter, rest: &[Letter]) -> bool {
+        let mut onset = onset_with(new, rest);
+        // every onset can end in a glide, like in bwe.no or pɾjeto
+        if onset.len() > 1
+            && onset
+                .last()
+                .is_some_and(|x| x.is_glide() || SPANISH_GLIDES.contains(&[x]))
+        {
+            onset.pop();
+        }
+        onset.len() <= 1 || SPANISH_ONSETS.contains(&onset)
+    }
+    fn is_diphthong(&self, nucleus: &[Letter]) -> bool {
+        SPANISH_INVENTORY.is_diphthong(nucleus)
+    }
+    fn inventory(&self) -> Option<&PhonemeInventory> {
+        Some(&SPANISH_INVENTORY)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn syllabify(input: &str) -> Vec<String> {
+        syls_from_word(
+            &Word::try_from(input).unwrap(),
+            &SpanishSyllableRule::default(),
+        )
+        .iter()
+        .map(|x| x.to_string())
+        .collect()
+    }
+
+    #[test]
+    fn onsets() {
+        assert_eq!(syllabify("ˈpweβlo"), ["ˈpwe", "βlo"]);
+        assert_eq!(syllabify("ˈkwatɾo"), ["ˈkwa", "tɾo"]);
+        assert_eq!(syllabify("esˈpaɲa"), ["es", "ˈpa", "ɲa"]);
+        assert_eq!(syllabify("pɾoˈxjekto"), ["pɾo", "ˈxjek", "to"]);
+    }
+
+    #[test]
+    fn diphthongs() {
+        assert_eq!(syllabify("ˈaiɾe"), ["ˈai", "ɾe"]);
+        assert_eq!(syllabify("ˈtio"), ["ˈtio"]);
+        // a syllable break marks the hiatus
+        assert_eq!(syllabify("ˈti.o"), ["ˈti", "o"]);
+        assert_eq!(syllabify("ˈkaos"), ["ˈka", "os"]);
+    }
+}
@@ -1,3 +1,4 @@
 pub mod fetching;
 pub mod find_rhyme;
 pub mod ipa;
+pub mod scansion;
@@ -0,0 +1,339 @@
+use super::fetching::IpaConverter;
+use super::ipa::{syls_from_word, Syllable, SyllableRule, SyllableStress};
+use anyhow::Context;
+use std::fmt;
+
+// scans lines into stress patterns like x / x / and finds the meter closest to them
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MetricalStress {
+    Stressed,
+    Unstressed,
+    // secondary stress, can fill either position of a foot
+    Ambiguous,
+}
+
+impl fmt::Display for MetricalStress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MetricalStress::Stressed => write!(f, "/"),
+            MetricalStress::Unstressed => write!(f, "x"),
+            MetricalStress::Ambiguous => write!(f, "\\"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Foot {
+    Iamb,
+    Trochee,
+    Anapest,
+    Dactyl,
+    Amphibrach,
+}
+
+const FOOT_LIST: [Foot; 5] = [
+    Foot::Iamb,
+    Foot::Trochee,
+    Foot::Anapest,
+    Foot::Dactyl,
+    Foot::Amphibrach,
+];
+
+const LINE_LENGTH_LIST: [&str; 8] = [
+    "monometer",
+    "dimeter",
+    "trimeter",
+    "tetrameter",
+    "pentameter",
+    "hexameter",
+    "heptameter",
+    "octameter",
+];
+
+// monosyllables that are unstressed in a line unless the meter wants otherwise
+const FUNCTION_WORD_LIST: [&str; 66] = [
+    "a", "an", "the", "and", "but", "or", "nor", "for", "so", "yet", "as", "at", "by", "in", "of",
+    "on", "to", "up", "from", "with", "than", "that", "if", "though", "i", "me", "my", "you",
+    "your", "he", "him", "his", "she", "her", "it", "its", "we", "us", "our", "they", "them",
+    "their", "is", "am", "are", "was", "were", "be", "been", "do", "does", "did", "has", "have",
+    "had", "can", "could", "will", "would", "shall", "should", "may", "might", "must", "i'm",
+    "it's",
+];
+
+impl Foot {
+    pub fn pattern(&self) -> &'static [MetricalStress] {
+        use MetricalStress::*;
+        match self {
+            Foot::Iamb => &[Unstressed, Stressed],
+            Foot::Trochee => &[Stressed, Unstressed],
+            Foot::Anapest => &[Unstressed, Unstressed, Stressed],
+            Foot::Dactyl => &[Stressed, Unstressed, Unstressed],
+            Foot::Amphibrach => &[Unstressed, Stressed, Unstressed],
+        }
+    }
+}
+
+impl fmt::Display for Foot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Foot::Iamb => write!(f, "iambic"),
+            Foot::Trochee => write!(f, "trochaic"),
+            Foot::Anapest => write!(f, "anapestic"),
+            Foot::Dactyl => write!(f, "dactylic"),
+            Foot::Amphibrach => write!(f, "amphibrachic"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Meter {
+    pub foot: Foot,
+    pub feet: usize,
+}
+
+impl Meter {
+    pub fn pattern(&self) -> Vec<MetricalStress> {
+        self.foot.pattern().repeat(self.feet)
+    }
+
+    // edit distance to the pattern of the meter, a wrong stress costs 1 and a missing or extra
+    // syllable 2, except for an unstressed one at the end of the line like in catalexis or a
+    // feminine ending, scaled so 0.0 is a perfect fit and 1.0 nothing in common
+    pub fn deviation(&self, pattern: &[MetricalStress]) -> f32 {
+        let ideal = self.pattern();
+        let (n, m) = (pattern.len(), ideal.len());
+        if n.max(m) == 0 {
+            return 0.0;
+        }
+        let mut cost = vec![vec![0; m + 1]; n + 1];
+        for i in 0..=n {
+            for j in 0..=m {
+                cost[i][j] = match (i, j) {
+                    (0, _) => 2 * j,
+                    (_, 0) => 2 * i,
+                    _ => {
+                        let stress = pattern[i - 1];
+                        let wrong = stress != MetricalStress::Ambiguous && stress != ideal[j - 1];
+                        (cost[i - 1][j - 1] + wrong as usize)
+                            .min(cost[i - 1][j] + 2)
+                            .min(cost[i][j - 1] + 2)
+                    }
+                };
+            }
+        }
+        let mut out = cost[n][m];
+        if n > 0 && pattern[n - 1] == MetricalStress::Unstressed {
+            out = out.min(cost[n - 1][m] + 1);
+        }
+        if m > 0 && ideal[m - 1] == MetricalStress::Unstressed {
+            out = out.min(cost[n][m - 1] + 1);
+        }
+        out as f32 / (2 * n.max(m)) as f32
+    }
+}
+
+impl fmt::Display for Meter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match LINE_LENGTH_LIST.get(self.feet.wrapping_sub(1)) {
+            Some(length) => write!(f, "{} {length}", self.foot),
+            None => write!(f, "{} with {} feet", self.foot, self.feet),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Scansion {
+    pub pattern: Vec<MetricalStress>,
+    pub meter: Meter,
+    pub deviation: f32,
+}
+
+impl Scansion {
+    // the meter with the lowest deviation, None for an empty line
+    pub fn new(pattern: Vec<MetricalStress>) -> Option<Self> {
+        if pattern.is_empty() {
+            return None;
+        }
+        let (meter, deviation) = FOOT_LIST
+            .iter()
+            .flat_map(|foot| (1..=LINE_LENGTH_LIST.len()).map(|feet| Meter { foot: *foot, feet }))
+            .map(|meter| (meter, meter.deviation(&pattern)))
+            .min_by(|a, b| a.1.total_cmp(&b.1))?;
+        Some(Self {
+            pattern,
+            meter,
+            deviation,
+        })
+    }
+
+    pub fn pattern_string(&self) -> String {
+        self.pattern
+            .iter()
+            .map(|x| x.to_string())
+            .collect::<Vec<String>>()
+            .join(" ")
+    }
+}
+
+pub fn is_function_word(word: &str) -> bool {
+    FUNCTION_WORD_LIST.contains(&word)
+}
+
+// dictionary stress, monosyllables are stressed unless they are function words
+pub fn word_stresses(text: &str, syllables: &[Syllable]) -> Vec<MetricalStress> {
+    if let [_] = syllables {
+        let stress = match syllables[0].stress {
+            _ if is_function_word(&text.to_lowercase()) => MetricalStress::Unstressed,
+            SyllableStress::Secondary => MetricalStress::Ambiguous,
+            _ => MetricalStress::Stressed,
+        };
+        return vec![stress];
+    }
+    // without any stress marks nothing is known about the word
+    let marked = syllables
+        .iter()
+        .any(|x| x.stress != SyllableStress::Unstressed);
+    syllables
+        .iter()
+        .map(|x| match x.stress {
+            SyllableStress::Primary => MetricalStress::Stressed,
+            SyllableStress::Secondary => MetricalStress::Ambiguous,
+            SyllableStress::Unstressed if marked => MetricalStress::Unstressed,
+            SyllableStress::Unstressed => MetricalStress::Ambiguous,
+        })
+        .collect()
+}
+
+pub fn scan_words<T: AsRef<str>>(words: &[(T, Vec<Syllable>)]) -> Option<Scansion> {
+    Scansion::new(
+        words
+            .iter()
+            .flat_map(|(text, syllables)| word_stresses(text.as_ref(), syllables))
+            .collect(),
+    )
+}
+
+// looks up every word of the line, the first transcription is used
+pub fn scan_line(
+    line: &str,
+    converter: &impl IpaConverter,
+    rule: &dyn SyllableRule,
+) -> anyhow::Result<Option<Scansion>> {
+    let mut words = vec![];
+    for text in line.split_whitespace() {
+        let text = text
+            .trim_matches(|x: char| !x.is_alphanumeric() && x != '\'')
+            .to_lowercase();
+        if text.is_empty() {
+            continue;
+        }
+        let ipa = converter.get_ipa_single(&text)?;
+        let word = ipa
+            .first()
+            .with_context(|| format!("no transcription for \"{text}\""))?;
+        let syllables = syls_from_word(word, rule);
+        words.push((text, syllables));
+    }
+    Ok(scan_words(&words))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ipa_utils::fetching::json::JsonLookupConverter;
+    use crate::ipa_utils::ipa::{english::EnglishSyllableRule, ParseOptions, Word};
+
+    fn words(line: &[(&'static str, &str)]) -> Vec<(&'static str, Vec<Syllable>)> {
+        line.iter()
+            .map(|(text, ipa)| {
+                (
+                    *text,
+                    syls_from_word(
+                        &Word::try_from(*ipa).unwrap(),
+                        &EnglishSyllableRule::default(),
+                    ),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn function_words_are_demoted() {
+        let scansion = scan_words(&words(&[
+            ("and", "ˈænd"),
+            ("the", "ˈðə"),
+            ("cat", "ˈkæt"),
+            ("is", "ˈɪz"),
+            ("asleep", "əˈslip"),
+        ]))
+        .unwrap();
+        assert_eq!(scansion.pattern_string(), "x x / x x /");
+        assert_eq!(scansion.meter.to_string(), "anapestic dimeter");
+        assert_eq!(scansion.deviation, 0.0);
+    }
+
+    #[test]
+    fn iambic_pentameter() {
+        // shall I compare thee to a summer's day
+        let scansion = scan_words(&words(&[
+            ("shall", "ˈʃæl"),
+            ("i", "ˈaɪ"),
+            ("compare", "kəmˈpɛɹ"),
+            ("thee", "ˈði"),
+            ("to", "ˈtu"),
+            ("a", "ˈə"),
+            ("summer's", "ˈsəmɝz"),
+            ("day", "ˈdeɪ"),
+        ]))
+        .unwrap();
+        assert_eq!(scansion.pattern_string(), "x x x / / x x / x /");
+        assert_eq!(
+            scansion.meter,
+            Meter {
+                foot: Foot::Iamb,
+                feet: 5
+            }
+        );
+        assert_eq!(scansion.deviation, 0.15);
+    }
+
+    #[test]
+    fn meters() {
+        use MetricalStress::*;
+        let trochaic = [Stressed, Unstressed].repeat(4);
+        let scansion = Scansion::new(trochaic).unwrap();
+        assert_eq!(scansion.meter.to_string(), "trochaic tetrameter");
+        assert_eq!(scansion.deviation, 0.0);
+        // secondary stress fits anywhere
+        let scansion = Scansion::new(vec![Stressed, Unstressed, Ambiguous, Unstressed]).unwrap();
+        assert_eq!(scansion.meter.to_string(), "trochaic dimeter");
+        assert_eq!(scansion.deviation, 0.0);
+        assert_eq!(Scansion::new(vec![]), None);
+    }
+
+    #[test]
+    fn scan_line_with_converter() {
+        let converter = JsonLookupConverter {
+            lookup_content: serde_json::json!({
+                "tyger": "/ˈtaɪɡɝ/",
+                "burning": "/ˈbɝnɪŋ/",
+                "bright": "/ˈbɹaɪt/",
+            }),
+            parse_options: ParseOptions::default(),
+        };
+        let rule = EnglishSyllableRule::default();
+        let scansion = scan_line("Tyger Tyger, burning bright,", &converter, &rule)
+            .unwrap()
+            .unwrap();
+        assert_eq!(scansion.pattern_string(), "/ x / x / x /");
+        // the last unstressed syllable is missing
+        assert_eq!(scansion.meter.to_string(), "trochaic tetrameter");
+        assert_eq!(scansion.deviation, 0.0625);
+        assert!(scan_line("unknown words", &converter, &rule).is_err());
+    }
+}
@@ -1 +1,19 @@
 extern crate rhymalize;
+
+use rhymalize::ipa_utils::ipa::{english::EnglishSyllableRule, evaluation::*};
+use std::fs;
+
+// the snapshot follows the rule's own convention, so only changes to its output are caught
+#[test]
+fn english_syllabification_snapshot() {
+    let snapshot = parse_gold(&fs::read_to_string("./syllables_en_snapshot.tsv").unwrap()).unwrap();
+    let evaluation = Evaluation::new(&snapshot, &EnglishSyllableRule::default());
+    // words in the baseline were syllabified right before
+    let baseline: Vec<String> = fs::read_to_string("./syllables_en_snapshot.baseline")
+        .unwrap()
+        .lines()
+        .map(|x| x.to_string())
+        .collect();
+    let regressions = evaluation.regressions(&baseline);
+    assert!(regressions.is_empty(), "{regressions:#?}");
+}